@@ -4,16 +4,87 @@ pub struct Puzzle {
     pub height: u32,
     pub sources: Vec<IntersectionOrEdge>,
     pub exits: Vec<IntersectionOrEdge>,
+    /// Gaps the line cannot pass through, which leave the cells on either side joined
     pub broken: Vec<IntersectionOrEdge>,
+    /// Parts of the grid which are not on the panel at all, such as notches and holes. A missing
+    /// intersection takes the edges around it with it, so it changes the outline of regions.
+    pub missing: Vec<IntersectionOrEdge>,
+    pub missing_cells: Vec<Pos>,
     pub dots: Vec<IntersectionOrEdge>,
+    pub line_dots: Vec<LineDot>,
+    /// Dots the line must pass through in this order
+    pub waypoints: Vec<IntersectionOrEdge>,
     pub squares: Vec<ColouredSymbol>,
     pub suns: Vec<ColouredSymbol>,
+    /// Cells whose symbol could not be made out, each with the symbols it might be
+    pub uncertain: Vec<UncertainCell>,
+    pub polyominoes: Vec<Polyomino>,
+    pub negative_polyominoes: Vec<Polyomino>,
+    pub eliminations: Vec<ColouredSymbol>,
+    pub triangles: Vec<Triangle>,
+    pub arrows: Vec<Arrow>,
+    pub numbers: Vec<Number>,
+    /// Number of lines drawn at once, each from its own source to its own exit. Ignored by
+    /// symmetry puzzles, which always have a line and its mirror image
+    pub line_count: usize,
+    /// Number of segments each line is made of, counting a segment the line starts or ends
+    /// partway along as a whole one
+    pub length: Option<LengthBound>,
+    pub symmetry: Option<Symmetry>,
+    pub topology: Topology,
+    pub lattice: Lattice,
+    /// Pairs of a symbol colour and the colour it appears as through tinted glass
+    pub colour_filter: Vec<(Colour, Colour)>,
+}
+
+impl Default for Puzzle {
+    fn default() -> Self {
+        Puzzle {
+            width: 0,
+            height: 0,
+            sources: Vec::new(),
+            exits: Vec::new(),
+            broken: Vec::new(),
+            missing: Vec::new(),
+            missing_cells: Vec::new(),
+            dots: Vec::new(),
+            line_dots: Vec::new(),
+            waypoints: Vec::new(),
+            squares: Vec::new(),
+            suns: Vec::new(),
+            uncertain: Vec::new(),
+            polyominoes: Vec::new(),
+            negative_polyominoes: Vec::new(),
+            eliminations: Vec::new(),
+            triangles: Vec::new(),
+            arrows: Vec::new(),
+            numbers: Vec::new(),
+            line_count: 1,
+            length: None,
+            symmetry: None,
+            topology: Topology::Flat,
+            lattice: Lattice::Square,
+            colour_filter: Vec::new(),
+        }
+    }
+}
+
+/// Panels solved one after another, where the exit a line leaves one panel through decides
+/// where the line on the next panel starts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanelChain {
+    pub panels: Vec<Puzzle>,
+    /// For each panel but the last, pairs of one of its exits and the source of the next panel
+    /// it leads to. Exits and sources which are not linked cannot be used, except for the exits
+    /// of the last panel and the sources of the first.
+    pub links: Vec<Vec<(IntersectionOrEdge, IntersectionOrEdge)>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum IntersectionOrEdge {
     Intersection(Pos),
     Edge(Edge),
+    ExitStub(ExitStub),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -26,6 +97,83 @@ pub struct Edge {
 pub enum EdgeDirection {
     Horizontal,
     Vertical,
+    /// From `pos` down and to the right, only on a triangular lattice
+    Diagonal,
+}
+
+/// A rounded exit sticking out of the border from the intersection at `pos`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExitStub {
+    pub pos: Pos,
+    pub dir: Direction,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LengthBound {
+    Exactly(u32),
+    AtMost(u32),
+    AtLeast(u32),
+}
+
+/// Makes the second of two lines a symmetric image of the first one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Symmetry {
+    /// Reflected across a vertical axis through the middle of the puzzle
+    Vertical,
+    /// Reflected across a horizontal axis through the middle of the puzzle
+    Horizontal,
+    /// Rotated by half a turn around the centre of the puzzle
+    Rotational,
+}
+
+/// A dot which must be collected by one particular line of a symmetry puzzle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineDot {
+    pub pos: IntersectionOrEdge,
+    pub line: LineColour,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineColour {
+    /// The line drawn by the player
+    Blue,
+    /// The symmetric image of the player's line
+    Yellow,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Topology {
+    Flat,
+    /// The left and right borders are joined, so intersections and vertical edges on the
+    /// seam use x = 0
+    Cylinder,
+    /// The faces of a cube of side `height / 3`, unfolded into a cross on a `4n` by `3n` grid
+    /// with the top and bottom faces above and below the second column. Cells outside the
+    /// cross are missing and the edges of the cross are joined up with the matching faces.
+    Cube,
+}
+
+/// The shape of the cells. Intersections and edges keep their square grid positions, so
+/// other lattices are drawn as distortions of it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Lattice {
+    Square,
+    /// Each square is cut in two by a diagonal edge from its top left to its bottom right.
+    /// Cell (2x, y) is the triangle below the diagonal of square (x, y), and (2x + 1, y) the
+    /// one above it.
+    Triangular,
+    /// Laid like a brick wall, with vertical edges only where x + y is even or on the left and
+    /// right sides, so each cell is a hexagon two squares wide, or half of one at the sides. A
+    /// cell is addressed by its leftmost square, though symbols may sit on either of its squares.
+    Hexagonal,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -40,7 +188,69 @@ pub struct ColouredSymbol {
     pub colour: Colour,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UncertainCell {
+    pub pos: Pos,
+    pub candidates: Vec<Candidate>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Candidate {
+    Empty,
+    Square(Colour),
+    Sun(Colour),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polyomino {
+    pub pos: Pos,
+    /// Cells covered by the piece, as offsets from the top left of its bounding box
+    pub shape: Vec<Pos>,
+    pub colour: Colour,
+    pub rotatable: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Triangle {
+    pub pos: Pos,
+    /// Number of the cell's sides the line must pass along
+    pub count: u32,
+    pub colour: Colour,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Arrow {
+    pub pos: Pos,
+    pub dir: ArrowDirection,
+    /// Number of times the line crosses the ray from the cell's centre in `dir`. Diagonal rays
+    /// only meet the grid at intersections, where the line crosses if it arrives on one side of
+    /// the ray and leaves on the other; touching the ray or ending on it doesn't count.
+    pub count: u32,
+    pub colour: Colour,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Number {
+    pub pos: Pos,
+    /// Number of cells in the region containing it
+    pub count: u32,
+    pub colour: Colour,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArrowDirection {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+/// Colours are compared by their shade, so a named colour equals `Rgb` at the same shade
+#[derive(Debug, Clone, Copy)]
 pub enum Colour {
     Black,
     White,
@@ -52,4 +262,32 @@ pub enum Colour {
     Turquoise,
     Blue,
     Purple,
+    Rgb(u8, u8, u8),
+}
+
+impl Colour {
+    /// The red, green and blue components of the colour's shade
+    pub fn rgb(&self) -> (u8, u8, u8) {
+        match *self {
+            Colour::Black => (0, 0, 0),
+            Colour::White => (255, 255, 255),
+            Colour::Pink => (255, 105, 180),
+            Colour::Red => (255, 0, 0),
+            Colour::Orange => (255, 165, 0),
+            Colour::Yellow => (255, 255, 0),
+            Colour::Green => (0, 128, 0),
+            Colour::Turquoise => (64, 224, 208),
+            Colour::Blue => (0, 0, 255),
+            Colour::Purple => (128, 0, 128),
+            Colour::Rgb(r, g, b) => (r, g, b),
+        }
+    }
+}
+
+impl PartialEq for Colour {
+    fn eq(&self, other: &Self) -> bool {
+        self.rgb() == other.rgb()
+    }
 }
+
+impl Eq for Colour {}
@@ -1,28 +1,53 @@
 use crate::puzzle::IntersectionOrEdge;
 use crate::EdgeDirection;
-use crate::{puzzle, Colour, Edge, Pos};
+use crate::{
+    puzzle, ArrowDirection, Candidate, Colour, Direction, Edge, ExitStub, Lattice, LengthBound,
+    LineColour, Pos, Symmetry, Topology,
+};
 use itertools::Itertools;
 use z3::ast::Ast;
 
 #[derive(Debug, Clone)]
 struct Node<'ctx> {
     broken: bool,
+    missing: bool,
     source: bool,
     exit: bool,
     dot: bool,
+    dot_line: Option<usize>,
     has_line: z3::ast::Bool<'ctx>,
-    line_index: z3::ast::Int<'ctx>,
-    source_used: z3::ast::Bool<'ctx>,
-    exit_used: z3::ast::Bool<'ctx>,
+    lines: Vec<NodeLine<'ctx>>,
+    eliminated: z3::ast::Bool<'ctx>,
 }
 
 impl<'ctx> Node<'ctx> {
-    fn default(ctx: &'ctx z3::Context) -> Self {
+    fn new(ctx: &'ctx z3::Context, num_lines: usize) -> Self {
         Node {
             broken: false,
+            missing: false,
             source: false,
             exit: false,
             dot: false,
+            dot_line: None,
+            has_line: z3::ast::Bool::fresh_const(ctx, "has_line"),
+            lines: (0..num_lines).map(|_| NodeLine::default(ctx)).collect(),
+            eliminated: z3::ast::Bool::fresh_const(ctx, "eliminated"),
+        }
+    }
+}
+
+/// The state of a node with respect to one of the lines drawn on the puzzle
+#[derive(Debug, Clone)]
+struct NodeLine<'ctx> {
+    has_line: z3::ast::Bool<'ctx>,
+    line_index: z3::ast::Int<'ctx>,
+    source_used: z3::ast::Bool<'ctx>,
+    exit_used: z3::ast::Bool<'ctx>,
+}
+
+impl<'ctx> NodeLine<'ctx> {
+    fn default(ctx: &'ctx z3::Context) -> Self {
+        NodeLine {
             has_line: z3::ast::Bool::fresh_const(ctx, "has_line"),
             line_index: z3::ast::Int::fresh_const(ctx, "line_index"),
             source_used: z3::ast::Bool::fresh_const(ctx, "source_used"),
@@ -35,62 +60,163 @@ impl<'ctx> Node<'ctx> {
 enum Symbol {
     Square(Colour),
     Sun(Colour),
+    Polyomino(Colour),
+    NegativePolyomino(Colour),
+    Elimination(Colour),
+    Triangle(Colour, u32),
+    Arrow(Colour, ArrowDirection, u32),
+    Number(Colour, u32),
 }
 
 #[derive(Debug, Clone)]
 struct Cell<'ctx> {
+    missing: bool,
     symbol: Option<Symbol>,
     region: z3::ast::Int<'ctx>,
+    region_distance: z3::ast::Int<'ctx>,
+    eliminated: z3::ast::Bool<'ctx>,
 }
 
 impl<'ctx> Cell<'ctx> {
     fn default(ctx: &'ctx z3::Context) -> Self {
         Cell {
+            missing: false,
             symbol: None,
-            region: z3::ast::Int::fresh_const(ctx, "exit_used"),
+            region: z3::ast::Int::fresh_const(ctx, "region"),
+            region_distance: z3::ast::Int::fresh_const(ctx, "region_distance"),
+            eliminated: z3::ast::Bool::fresh_const(ctx, "eliminated"),
         }
     }
 }
 
+#[derive(Debug, Clone)]
+struct Polyomino<'ctx> {
+    pos: Pos,
+    negative: bool,
+    area: usize,
+    placements: Vec<PolyominoPlacement>,
+    placement: z3::ast::Int<'ctx>,
+    cancelled: z3::ast::Bool<'ctx>,
+}
+
 #[derive(Debug)]
 struct PuzzleModel<'ctx> {
     ctx: &'ctx z3::Context,
     width: u32,
     height: u32,
+    symmetry: Option<Symmetry>,
+    topology: Topology,
+    lattice: Lattice,
+    num_lines: usize,
     intersections: Vec<Vec<Node<'ctx>>>,
     horizontal_edges: Vec<Vec<Node<'ctx>>>,
     vertical_edges: Vec<Vec<Node<'ctx>>>,
+    diagonal_edges: Vec<Vec<Node<'ctx>>>,
     cells: Vec<Vec<Cell<'ctx>>>,
-    num_regions: z3::ast::Int<'ctx>,
+    exit_stubs: Vec<(ExitStub, Node<'ctx>)>,
+    /// Intersections and edges which are joined to another one, with the one standing in for both
+    seams: Vec<(IntersectionOrEdge, IntersectionOrEdge)>,
+    polyominoes: Vec<Polyomino<'ctx>>,
+    waypoints: Vec<IntersectionOrEdge>,
+    colour_filter: Vec<(Colour, Colour)>,
+    length: Option<LengthBound>,
 }
 
 impl<'ctx> PuzzleModel<'ctx> {
+    /// Number of intersections (and vertical edges) in each row
+    fn columns(&self) -> u32 {
+        match self.topology {
+            Topology::Flat | Topology::Cube => self.width + 1,
+            Topology::Cylinder => self.width,
+        }
+    }
+
+    /// Moves an x coordinate on the right border of a cylinder onto the seam at x = 0
+    fn wrap_x(&self, x: u32) -> u32 {
+        match self.topology {
+            Topology::Cylinder if x == self.width => 0,
+            _ => x,
+        }
+    }
+
+    fn canonical(&self, intersection_or_edge: &IntersectionOrEdge) -> IntersectionOrEdge {
+        self.seams
+            .iter()
+            .find(|(alias, _)| alias == intersection_or_edge)
+            .map(|(_, canonical)| canonical.clone())
+            .unwrap_or_else(|| intersection_or_edge.clone())
+    }
+
+    /// Every intersection or edge joined to a canonical one, including itself
+    fn aliases(&self, intersection_or_edge: &IntersectionOrEdge) -> Vec<IntersectionOrEdge> {
+        let mut aliases = vec![intersection_or_edge.clone()];
+        aliases.extend(
+            self.seams
+                .iter()
+                .filter(|(_, canonical)| canonical == intersection_or_edge)
+                .map(|(alias, _)| alias.clone()),
+        );
+        aliases
+    }
+
+    fn is_alias(&self, intersection_or_edge: &IntersectionOrEdge) -> bool {
+        self.seams
+            .iter()
+            .any(|(alias, _)| alias == intersection_or_edge)
+    }
+
+    fn canonical_pos(&self, pos: &Pos) -> Pos {
+        match self.canonical(&IntersectionOrEdge::Intersection(pos.clone())) {
+            IntersectionOrEdge::Intersection(pos) => pos,
+            _ => unreachable!(),
+        }
+    }
+
+    fn canonical_edge(&self, edge: &Edge) -> Edge {
+        match self.canonical(&IntersectionOrEdge::Edge(edge.clone())) {
+            IntersectionOrEdge::Edge(edge) => edge,
+            _ => unreachable!(),
+        }
+    }
+
     fn intersection(&self, pos: &Pos) -> &Node<'ctx> {
-        &self.intersections[pos.y as usize][pos.x as usize]
+        let pos = self.canonical_pos(pos);
+        &self.intersections[pos.y as usize][self.wrap_x(pos.x) as usize]
     }
 
     fn intersection_mut(&mut self, pos: &Pos) -> &mut Node<'ctx> {
-        &mut self.intersections[pos.y as usize][pos.x as usize]
+        let pos = self.canonical_pos(pos);
+        let x = self.wrap_x(pos.x);
+        &mut self.intersections[pos.y as usize][x as usize]
     }
 
     fn edge(&self, edge: &Edge) -> &Node<'ctx> {
+        let edge = &self.canonical_edge(edge);
         match edge.dir {
             EdgeDirection::Horizontal => {
                 &self.horizontal_edges[edge.pos.y as usize][edge.pos.x as usize]
             }
             EdgeDirection::Vertical => {
-                &self.vertical_edges[edge.pos.y as usize][edge.pos.x as usize]
+                &self.vertical_edges[edge.pos.y as usize][self.wrap_x(edge.pos.x) as usize]
+            }
+            EdgeDirection::Diagonal => {
+                &self.diagonal_edges[edge.pos.y as usize][edge.pos.x as usize]
             }
         }
     }
 
     fn edge_mut(&mut self, edge: &Edge) -> &mut Node<'ctx> {
+        let edge = &self.canonical_edge(edge);
         match edge.dir {
             EdgeDirection::Horizontal => {
                 &mut self.horizontal_edges[edge.pos.y as usize][edge.pos.x as usize]
             }
             EdgeDirection::Vertical => {
-                &mut self.vertical_edges[edge.pos.y as usize][edge.pos.x as usize]
+                let x = self.wrap_x(edge.pos.x);
+                &mut self.vertical_edges[edge.pos.y as usize][x as usize]
+            }
+            EdgeDirection::Diagonal => {
+                &mut self.diagonal_edges[edge.pos.y as usize][edge.pos.x as usize]
             }
         }
     }
@@ -99,6 +225,12 @@ impl<'ctx> PuzzleModel<'ctx> {
         match intersection_or_edge {
             IntersectionOrEdge::Intersection(intersection) => self.intersection(intersection),
             IntersectionOrEdge::Edge(edge) => self.edge(edge),
+            IntersectionOrEdge::ExitStub(stub) => self
+                .exit_stubs
+                .iter()
+                .find(|(other, _)| other == stub)
+                .map(|(_, node)| node)
+                .unwrap_or_else(|| panic!("No exit stub {stub:?}")),
         }
     }
 
@@ -106,6 +238,12 @@ impl<'ctx> PuzzleModel<'ctx> {
         match intersection_or_edge {
             IntersectionOrEdge::Intersection(intersection) => self.intersection_mut(intersection),
             IntersectionOrEdge::Edge(edge) => self.edge_mut(edge),
+            IntersectionOrEdge::ExitStub(stub) => self
+                .exit_stubs
+                .iter_mut()
+                .find(|(other, _)| other == stub)
+                .map(|(_, node)| node)
+                .unwrap_or_else(|| panic!("No exit stub {stub:?}")),
         }
     }
 
@@ -118,18 +256,37 @@ impl<'ctx> PuzzleModel<'ctx> {
     }
 
     fn adjacent(&self, intersection_or_edge: &IntersectionOrEdge) -> Vec<IntersectionOrEdge> {
-        match intersection_or_edge {
-            IntersectionOrEdge::Intersection(intersection) => self
-                .adjacent_edges(intersection)
-                .into_iter()
-                .map(IntersectionOrEdge::Edge)
-                .collect(),
-            IntersectionOrEdge::Edge(edge) => self
-                .adjacent_intersections(edge)
-                .into_iter()
-                .map(IntersectionOrEdge::Intersection)
-                .collect(),
+        let mut result: Vec<IntersectionOrEdge> = Vec::new();
+        for alias in self.aliases(&self.canonical(intersection_or_edge)) {
+            let adjacent: Vec<_> = match &alias {
+                IntersectionOrEdge::Intersection(intersection) => self
+                    .adjacent_edges(intersection)
+                    .into_iter()
+                    .map(IntersectionOrEdge::Edge)
+                    .chain(
+                        self.exit_stubs
+                            .iter()
+                            .filter(|(stub, _)| stub.pos == *intersection)
+                            .map(|(stub, _)| IntersectionOrEdge::ExitStub(stub.clone())),
+                    )
+                    .collect(),
+                IntersectionOrEdge::ExitStub(stub) => {
+                    vec![IntersectionOrEdge::Intersection(stub.pos.clone())]
+                }
+                IntersectionOrEdge::Edge(edge) => self
+                    .adjacent_intersections(edge)
+                    .into_iter()
+                    .map(IntersectionOrEdge::Intersection)
+                    .collect(),
+            };
+            for adj in adjacent {
+                let adj = self.canonical(&adj);
+                if !self.node(&adj).missing && !result.contains(&adj) {
+                    result.push(adj);
+                }
+            }
         }
+        result
     }
 
     fn adjacent_nodes(&self, intersection_or_edge: &IntersectionOrEdge) -> Vec<&Node> {
@@ -141,6 +298,30 @@ impl<'ctx> PuzzleModel<'ctx> {
     }
 
     fn adjacent_cells(&self, edge: &Edge) -> Vec<Pos> {
+        let mut cells: Vec<Pos> = Vec::new();
+        let canonical = IntersectionOrEdge::Edge(self.canonical_edge(edge));
+        for alias in self.aliases(&canonical) {
+            if let IntersectionOrEdge::Edge(alias) = alias {
+                for cell in self.edge_cells(&alias) {
+                    if !self.cell(&cell).missing && !cells.contains(&cell) {
+                        cells.push(cell);
+                    }
+                }
+            }
+        }
+        cells
+    }
+
+    /// The cells on either side of an edge, ignoring any seams
+    fn edge_cells(&self, edge: &Edge) -> Vec<Pos> {
+        match self.lattice {
+            Lattice::Square => self.square_edge_cells(edge),
+            Lattice::Triangular => self.triangular_edge_cells(edge),
+            Lattice::Hexagonal => self.hexagonal_edge_cells(edge),
+        }
+    }
+
+    fn square_edge_cells(&self, edge: &Edge) -> Vec<Pos> {
         let mut cells = Vec::new();
         match edge.dir {
             // 00112
@@ -151,6 +332,11 @@ impl<'ctx> PuzzleModel<'ctx> {
                         x: edge.pos.x - 1,
                         y: edge.pos.y,
                     });
+                } else if self.topology == Topology::Cylinder {
+                    cells.push(Pos {
+                        x: self.width - 1,
+                        y: edge.pos.y,
+                    });
                 }
                 if edge.pos.x < self.width {
                     cells.push(Pos {
@@ -173,11 +359,303 @@ impl<'ctx> PuzzleModel<'ctx> {
                     });
                 }
             }
+            EdgeDirection::Diagonal => unreachable!("No diagonal edges on a square lattice"),
+        }
+        cells
+    }
+
+    fn triangular_edge_cells(&self, edge: &Edge) -> Vec<Pos> {
+        let Pos { x, y } = edge.pos;
+        let mut cells = Vec::new();
+        match edge.dir {
+            EdgeDirection::Vertical => {
+                if x > 0 {
+                    cells.push(Pos { x: 2 * x - 1, y });
+                }
+                if x < self.width {
+                    cells.push(Pos { x: 2 * x, y });
+                }
+            }
+            EdgeDirection::Horizontal => {
+                if y > 0 {
+                    cells.push(Pos { x: 2 * x, y: y - 1 });
+                }
+                if y < self.height {
+                    cells.push(Pos { x: 2 * x + 1, y });
+                }
+            }
+            EdgeDirection::Diagonal => {
+                cells.push(Pos { x: 2 * x, y });
+                cells.push(Pos { x: 2 * x + 1, y });
+            }
+        }
+        cells
+    }
+
+    fn hexagonal_edge_cells(&self, edge: &Edge) -> Vec<Pos> {
+        let Pos { x, y } = edge.pos;
+        let mut cells = Vec::new();
+        match edge.dir {
+            EdgeDirection::Vertical => {
+                if x > 0 {
+                    cells.push(Pos {
+                        x: Self::hexagon_start(x - 1, y),
+                        y,
+                    });
+                }
+                if x < self.width {
+                    cells.push(Pos { x, y });
+                }
+            }
+            EdgeDirection::Horizontal => {
+                if y > 0 {
+                    cells.push(Pos {
+                        x: Self::hexagon_start(x, y - 1),
+                        y: y - 1,
+                    });
+                }
+                if y < self.height {
+                    cells.push(Pos {
+                        x: Self::hexagon_start(x, y),
+                        y,
+                    });
+                }
+            }
+            EdgeDirection::Diagonal => unreachable!("No diagonal edges on a hexagonal lattice"),
         }
         cells
     }
 
+    /// Whether the lattice has this edge at all
+    fn edge_exists(&self, edge: &Edge) -> bool {
+        match (&self.lattice, &edge.dir) {
+            // The left and right sides of the panel are straight, cutting hexagons in half
+            (Lattice::Hexagonal, EdgeDirection::Vertical) => {
+                edge.pos.x == 0
+                    || edge.pos.x == self.width
+                    || (edge.pos.x + edge.pos.y).is_multiple_of(2)
+            }
+            (Lattice::Square | Lattice::Hexagonal, EdgeDirection::Diagonal) => false,
+            _ => true,
+        }
+    }
+
+    fn touching_cells(&self, intersection_or_edge: &IntersectionOrEdge) -> Vec<Pos> {
+        match intersection_or_edge {
+            IntersectionOrEdge::Intersection(pos) => {
+                let mut cells = Vec::new();
+                let canonical = IntersectionOrEdge::Intersection(self.canonical_pos(pos));
+                for alias in self.aliases(&canonical) {
+                    if let IntersectionOrEdge::Intersection(alias) = alias {
+                        for edge in self.adjacent_edges(&alias) {
+                            for cell in self.adjacent_cells(&edge) {
+                                if !cells.contains(&cell) {
+                                    cells.push(cell);
+                                }
+                            }
+                        }
+                    }
+                }
+                cells
+            }
+            IntersectionOrEdge::Edge(edge) => self.adjacent_cells(edge),
+            IntersectionOrEdge::ExitStub(_) => Vec::new(),
+        }
+    }
+
+    /// The edges crossed by a ray from the centre of a cell, or the intersections it passes
+    /// through for diagonal rays
+    fn arrow_ray(&self, pos: &Pos, dir: ArrowDirection) -> Vec<IntersectionOrEdge> {
+        let (dx, dy): (i64, i64) = match dir {
+            ArrowDirection::Up => (0, -1),
+            ArrowDirection::UpRight => (1, -1),
+            ArrowDirection::Right => (1, 0),
+            ArrowDirection::DownRight => (1, 1),
+            ArrowDirection::Down => (0, 1),
+            ArrowDirection::DownLeft => (-1, 1),
+            ArrowDirection::Left => (-1, 0),
+            ArrowDirection::UpLeft => (-1, -1),
+        };
+        let mut ray = Vec::new();
+        let mut x = pos.x as i64 + if dx > 0 { 1 } else { 0 };
+        let mut y = pos.y as i64 + if dy > 0 { 1 } else { 0 };
+        loop {
+            if y < 0 || y > self.height as i64 {
+                break;
+            }
+            match self.topology {
+                Topology::Flat | Topology::Cube => {
+                    if x < 0 || x > self.width as i64 {
+                        break;
+                    }
+                }
+                Topology::Cylinder => {
+                    // A horizontal ray goes round the cylinder once
+                    if dy == 0 && ray.len() == self.width as usize {
+                        break;
+                    }
+                    x = x.rem_euclid(self.width as i64);
+                }
+            }
+            let pos = Pos {
+                x: x as u32,
+                y: y as u32,
+            };
+            ray.push(match (dx, dy) {
+                (0, _) => IntersectionOrEdge::Edge(Edge {
+                    pos,
+                    dir: EdgeDirection::Horizontal,
+                }),
+                (_, 0) => IntersectionOrEdge::Edge(Edge {
+                    pos,
+                    dir: EdgeDirection::Vertical,
+                }),
+                _ => IntersectionOrEdge::Intersection(pos),
+            });
+            x += dx;
+            y += dy;
+        }
+        ray
+    }
+
+    /// Whether the line crosses a diagonal ray at an intersection on it, entering from one side
+    /// of the ray and leaving on the other, rather than only touching it or ending there
+    fn diagonal_crossing(&self, pos: &Pos, dir: ArrowDirection) -> z3::ast::Bool<'ctx> {
+        // Up and down edges always lie on opposite sides of a diagonal ray; left and right edges
+        // fall on the same side as up when the ray runs up-left to down-right, or down otherwise
+        let down_right = matches!(dir, ArrowDirection::DownRight | ArrowDirection::UpLeft);
+        let (upper, lower): (Vec<_>, Vec<_>) = self
+            .square_adjacent_edges(pos)
+            .into_iter()
+            .partition(|edge| match edge.dir {
+                EdgeDirection::Vertical => edge.pos.y < pos.y,
+                _ => (edge.pos.x == pos.x) == down_right,
+            });
+        let lines = |edges: Vec<Edge>| {
+            edges
+                .into_iter()
+                .map(|edge| self.edge(&edge).has_line.clone())
+                .collect::<Vec<_>>()
+        };
+        let upper = lines(upper);
+        let lower = lines(lower);
+        let one = |lines: &[z3::ast::Bool<'ctx>]| {
+            z3::ast::Bool::pb_eq(
+                self.ctx,
+                &lines.iter().map(|line| (line, 1)).collect::<Vec<_>>(),
+                1,
+            )
+        };
+        z3::ast::Bool::and(self.ctx, &[&one(&upper), &one(&lower)])
+    }
+
+    fn cell_edges(&self, pos: &Pos) -> Vec<Edge> {
+        match self.lattice {
+            Lattice::Square => self.square_cell_edges(pos),
+            Lattice::Triangular => self.triangular_cell_edges(pos),
+            Lattice::Hexagonal => self.hexagonal_cell_edges(pos),
+        }
+    }
+
+    fn square_cell_edges(&self, pos: &Pos) -> Vec<Edge> {
+        vec![
+            Edge {
+                pos: pos.clone(),
+                dir: EdgeDirection::Horizontal,
+            },
+            Edge {
+                pos: Pos {
+                    x: pos.x,
+                    y: pos.y + 1,
+                },
+                dir: EdgeDirection::Horizontal,
+            },
+            Edge {
+                pos: pos.clone(),
+                dir: EdgeDirection::Vertical,
+            },
+            Edge {
+                pos: Pos {
+                    x: self.wrap_x(pos.x + 1),
+                    y: pos.y,
+                },
+                dir: EdgeDirection::Vertical,
+            },
+        ]
+    }
+
+    fn triangular_cell_edges(&self, pos: &Pos) -> Vec<Edge> {
+        let Pos { x, y } = *pos;
+        let square = Pos { x: x / 2, y };
+        let (horizontal, vertical) = if x % 2 == 0 {
+            (Pos { x: x / 2, y: y + 1 }, square.clone())
+        } else {
+            (square.clone(), Pos { x: x / 2 + 1, y })
+        };
+        vec![
+            Edge {
+                pos: horizontal,
+                dir: EdgeDirection::Horizontal,
+            },
+            Edge {
+                pos: vertical,
+                dir: EdgeDirection::Vertical,
+            },
+            Edge {
+                pos: square,
+                dir: EdgeDirection::Diagonal,
+            },
+        ]
+    }
+
+    fn hexagonal_cell_edges(&self, pos: &Pos) -> Vec<Edge> {
+        let Pos { x, y } = *pos;
+        let squares: Vec<u32> = (x..(x + 2).min(self.width))
+            .filter(|&square| Self::hexagon_start(square, y) == x)
+            .collect();
+        let mut edges = Vec::new();
+        for &square in &squares {
+            for y in [y, y + 1] {
+                edges.push(Edge {
+                    pos: Pos { x: square, y },
+                    dir: EdgeDirection::Horizontal,
+                });
+            }
+        }
+        for x in [x, squares[squares.len() - 1] + 1] {
+            edges.push(Edge {
+                pos: Pos { x, y },
+                dir: EdgeDirection::Vertical,
+            });
+        }
+        edges.retain(|edge| self.edge_exists(edge));
+        edges
+    }
+
     fn adjacent_edges(&self, pos: &Pos) -> Vec<Edge> {
+        let mut adjacent_edges = self.square_adjacent_edges(pos);
+        if self.lattice == Lattice::Triangular {
+            if pos.x > 0 && pos.y > 0 {
+                adjacent_edges.push(Edge {
+                    pos: Pos {
+                        x: pos.x - 1,
+                        y: pos.y - 1,
+                    },
+                    dir: EdgeDirection::Diagonal,
+                });
+            }
+            if pos.x < self.width && pos.y < self.height {
+                adjacent_edges.push(Edge {
+                    pos: pos.clone(),
+                    dir: EdgeDirection::Diagonal,
+                });
+            }
+        }
+        adjacent_edges.retain(|edge| self.edge_exists(edge));
+        adjacent_edges
+    }
+
+    fn square_adjacent_edges(&self, pos: &Pos) -> Vec<Edge> {
         let mut adjacent_edges: Vec<Edge> = Vec::new();
         // Left edge
         if pos.x > 0 {
@@ -188,6 +666,14 @@ impl<'ctx> PuzzleModel<'ctx> {
                 },
                 dir: EdgeDirection::Horizontal,
             });
+        } else if self.topology == Topology::Cylinder {
+            adjacent_edges.push(Edge {
+                pos: Pos {
+                    x: self.width - 1,
+                    y: pos.y,
+                },
+                dir: EdgeDirection::Horizontal,
+            });
         }
         // Top edge
         if pos.y > 0 {
@@ -224,33 +710,131 @@ impl<'ctx> PuzzleModel<'ctx> {
                 y: edge.pos.y + 1,
             },
             EdgeDirection::Horizontal => Pos {
-                x: edge.pos.x + 1,
+                x: self.wrap_x(edge.pos.x + 1),
                 y: edge.pos.y,
             },
+            EdgeDirection::Diagonal => Pos {
+                x: edge.pos.x + 1,
+                y: edge.pos.y + 1,
+            },
         };
         vec![start, end]
     }
 
     fn from_puzzle(p: &puzzle::Puzzle, ctx: &'ctx z3::Context) -> Self {
+        let num_lines = if p.symmetry.is_some() {
+            2
+        } else {
+            p.line_count
+        };
+        assert!(num_lines >= 1, "Puzzles have at least one line");
+        assert!(
+            p.topology != Topology::Cube || p.arrows.is_empty(),
+            "Arrows cannot follow their ray around a cube"
+        );
+        assert!(
+            p.topology != Topology::Cube
+                || (p.polyominoes.is_empty() && p.negative_polyominoes.is_empty()),
+            "Polyominoes cannot be placed across the folds of a cube"
+        );
+        assert!(
+            p.topology != Topology::Cube || matches!(p.symmetry, None | Some(Symmetry::Horizontal)),
+            "Only horizontal symmetry maps the net of a cube onto itself"
+        );
+        if p.lattice != Lattice::Square {
+            assert!(
+                p.topology == Topology::Flat
+                    && p.symmetry.is_none()
+                    && p.polyominoes.is_empty()
+                    && p.negative_polyominoes.is_empty()
+                    && p.arrows.is_empty(),
+                "Only flat puzzles without symmetry, polyominoes or arrows can use another lattice"
+            );
+        }
+        assert!(
+            p.uncertain.is_empty(),
+            "Puzzles with uncertain cells are solved with solve_uncertain or solvable_assignments"
+        );
+        let cell_columns = match p.lattice {
+            Lattice::Triangular => 2 * p.width,
+            Lattice::Square | Lattice::Hexagonal => p.width,
+        };
+        let node = || Node::new(ctx, num_lines);
         let mut model = PuzzleModel {
             ctx,
             width: p.width,
             height: p.height,
-            intersections: Self::create_2d_vec(p.width + 1, p.height + 1, || Node::default(ctx)),
-            horizontal_edges: Self::create_2d_vec(p.width, p.height + 1, || Node::default(ctx)),
-            vertical_edges: Self::create_2d_vec(p.width + 1, p.height, || Node::default(ctx)),
-            cells: Self::create_2d_vec(p.width, p.height, || Cell::default(ctx)),
-            num_regions: z3::ast::Int::fresh_const(ctx, "num_regions"),
+            symmetry: p.symmetry.clone(),
+            topology: p.topology.clone(),
+            lattice: p.lattice.clone(),
+            num_lines,
+            intersections: Self::create_2d_vec(p.width + 1, p.height + 1, node),
+            horizontal_edges: Self::create_2d_vec(p.width, p.height + 1, node),
+            vertical_edges: Self::create_2d_vec(p.width + 1, p.height, node),
+            diagonal_edges: Self::create_2d_vec(p.width, p.height, node),
+            cells: Self::create_2d_vec(cell_columns, p.height, || Cell::default(ctx)),
+            exit_stubs: Vec::new(),
+            seams: Vec::new(),
+            polyominoes: Vec::new(),
+            waypoints: p.waypoints.clone(),
+            colour_filter: p.colour_filter.clone(),
+            length: p.length.clone(),
         };
+        if model.topology == Topology::Cube {
+            model.add_cube_net();
+        }
+        if model.lattice == Lattice::Hexagonal {
+            model.add_hexagons();
+        }
         model.add_broken(&p.broken);
+        model.add_missing(&p.missing, &p.missing_cells);
         model.add_sources(&p.sources);
         model.add_exits(&p.exits);
         model.add_dots(&p.dots);
+        model.add_line_dots(&p.line_dots);
         model.add_squares(&p.squares);
         model.add_suns(&p.suns);
+        model.add_polyominoes(&p.polyominoes, false);
+        model.add_polyominoes(&p.negative_polyominoes, true);
+        model.add_eliminations(&p.eliminations);
+        model.add_triangles(&p.triangles);
+        model.add_arrows(&p.arrows);
+        model.add_numbers(&p.numbers);
         model
     }
 
+    /// Makes this model draw the same lines as `other`, keeping its own symbols and regions
+    fn share_lines(&mut self, other: &PuzzleModel<'ctx>) {
+        self.num_lines = other.num_lines;
+        let nodes = self
+            .intersections
+            .iter_mut()
+            .chain(self.horizontal_edges.iter_mut())
+            .chain(self.vertical_edges.iter_mut())
+            .chain(self.diagonal_edges.iter_mut())
+            .flatten();
+        let other_nodes = other
+            .intersections
+            .iter()
+            .chain(other.horizontal_edges.iter())
+            .chain(other.vertical_edges.iter())
+            .chain(other.diagonal_edges.iter())
+            .flatten();
+        for (node, other_node) in nodes.zip(other_nodes) {
+            node.has_line = other_node.has_line.clone();
+            node.lines = other_node.lines.clone();
+        }
+        for (stub, node) in self.exit_stubs.iter_mut() {
+            let (_, other_node) = other
+                .exit_stubs
+                .iter()
+                .find(|(other_stub, _)| other_stub == stub)
+                .unwrap_or_else(|| panic!("No exit stub {stub:?} to share"));
+            node.has_line = other_node.has_line.clone();
+            node.lines = other_node.lines.clone();
+        }
+    }
+
     fn create_2d_vec<T, F: Fn() -> T>(width: u32, height: u32, constructor: F) -> Vec<Vec<T>> {
         (0..(height + 1))
             .map(|_| (0..(width + 1)).map(|_| constructor()).collect())
@@ -263,65 +847,420 @@ impl<'ctx> PuzzleModel<'ctx> {
         }
     }
 
-    fn add_sources(&mut self, sources: &Vec<IntersectionOrEdge>) {
-        for s in sources {
-            self.node_mut(s).source = true;
-        }
+    /// Whether any of the lines ends at this exit
+    fn exit_used(&self, exit: &IntersectionOrEdge) -> z3::ast::Bool<'ctx> {
+        self.node(exit)
+            .lines
+            .iter()
+            .map(|line| line.exit_used.clone())
+            .reduce(|acc, used| acc | used)
+            .unwrap_or_else(|| z3::ast::Bool::from_bool(self.ctx, false))
     }
 
-    fn add_exits(&mut self, exits: &Vec<IntersectionOrEdge>) {
-        for e in exits {
-            self.node_mut(e).exit = true;
-        }
+    /// Whether any of the lines starts at this source
+    fn source_used(&self, source: &IntersectionOrEdge) -> z3::ast::Bool<'ctx> {
+        self.node(source)
+            .lines
+            .iter()
+            .map(|line| line.source_used.clone())
+            .reduce(|acc, used| acc | used)
+            .unwrap_or_else(|| z3::ast::Bool::from_bool(self.ctx, false))
     }
 
-    fn add_dots(&mut self, dots: &Vec<IntersectionOrEdge>) {
-        for d in dots {
-            self.node_mut(d).dot = true;
-        }
+    /// Intersections on the outside of the panel, where a hidden exit could be
+    fn border_intersections(&self) -> Vec<IntersectionOrEdge> {
+        self.intersections()
+            .into_iter()
+            .filter(|pos| {
+                let node = self.intersection(pos);
+                !node.missing
+                    && !node.broken
+                    && !node.source
+                    && self
+                        .adjacent_edges(pos)
+                        .iter()
+                        .any(|edge| !self.edge(edge).missing && self.adjacent_cells(edge).len() < 2)
+            })
+            .map(IntersectionOrEdge::Intersection)
+            .collect()
     }
 
-    fn add_squares(&mut self, squares: &[puzzle::ColouredSymbol]) {
-        for s in squares {
-            self.cell_mut(&s.pos).symbol = Some(Symbol::Square(s.colour));
+    /// Only the leftmost square of each hexagon stands for the cell
+    fn add_hexagons(&mut self) {
+        for x in 0..self.width {
+            for y in 0..self.height {
+                if Self::hexagon_start(x, y) != x {
+                    self.cell_mut(&Pos { x, y }).missing = true;
+                }
+            }
         }
     }
 
-    fn add_suns(&mut self, suns: &[puzzle::ColouredSymbol]) {
-        for s in suns {
-            self.cell_mut(&s.pos).symbol = Some(Symbol::Sun(s.colour));
+    /// The leftmost square of the hexagon covering square (x, y)
+    fn hexagon_start(x: u32, y: u32) -> u32 {
+        if x == 0 || (x + y).is_multiple_of(2) {
+            x
+        } else {
+            x - 1
         }
     }
 
-    fn intersections_and_edges(&self) -> Vec<IntersectionOrEdge> {
-        let mut intersections: Vec<_> = self
-            .intersections()
-            .into_iter()
-            .map(IntersectionOrEdge::Intersection)
-            .collect();
-        let mut edges = self
-            .edges()
-            .into_iter()
-            .map(IntersectionOrEdge::Edge)
-            .collect();
-        intersections.append(&mut edges);
-        intersections
-    }
-
-    fn intersections(&self) -> Vec<Pos> {
-        let mut result: Vec<Pos> = Vec::new();
-        for x in 0..(self.width + 1) {
-            for y in 0..(self.height + 1) {
-                result.push(Pos { x, y });
+    /// Cuts the cross out of the grid and joins each edge of it to the one it folds onto
+    fn add_cube_net(&mut self) {
+        let n = self.height / 3;
+        assert!(
+            n > 0 && self.height == 3 * n && self.width == 4 * n,
+            "A cube net is 4n by 3n cells"
+        );
+        let on_net = |x: i64, y: i64| {
+            let n = n as i64;
+            (n..2 * n).contains(&y) && (0..4 * n).contains(&x)
+                || (0..3 * n).contains(&y) && (n..2 * n).contains(&x)
+        };
+        for x in 0..self.width {
+            for y in 0..self.height {
+                if !on_net(x as i64, y as i64) {
+                    self.cell_mut(&Pos { x, y }).missing = true;
+                }
             }
         }
-        result
-    }
-
-    fn edges(&self) -> Vec<Edge> {
-        let mut result: Vec<Edge> = Vec::new();
-        for x in 0..(self.width + 1) {
-            for y in 0..(self.height + 1) {
+        for pos in self.intersections() {
+            let (x, y) = (pos.x as i64, pos.y as i64);
+            if ![(x - 1, y - 1), (x, y - 1), (x - 1, y), (x, y)]
+                .iter()
+                .any(|&(x, y)| on_net(x, y))
+            {
+                self.intersection_mut(&pos).missing = true;
+            }
+        }
+        for edge in self.edges() {
+            if self
+                .edge_cells(&edge)
+                .iter()
+                .all(|pos| self.cell(pos).missing)
+            {
+                self.edge_mut(&edge).missing = true;
+            }
+        }
+
+        let n = n as i64;
+        // Each seam runs from a start along a step on both sides of the fold
+        let seams = [
+            ((n, 0), (0, 1), (0, n), (1, 0)),
+            ((2 * n, 0), (0, 1), (3 * n, n), (-1, 0)),
+            ((n, 0), (1, 0), (4 * n, n), (-1, 0)),
+            ((4 * n, n), (0, 1), (0, n), (0, 1)),
+            ((n, 2 * n), (0, 1), (n, 2 * n), (-1, 0)),
+            ((2 * n, 2 * n), (0, 1), (2 * n, 2 * n), (1, 0)),
+            ((n, 3 * n), (1, 0), (4 * n, 2 * n), (-1, 0)),
+        ];
+        for (p_start, p_step, q_start, q_step) in seams {
+            let point = |start: (i64, i64), step: (i64, i64), t: i64| Pos {
+                x: (start.0 + t * step.0) as u32,
+                y: (start.1 + t * step.1) as u32,
+            };
+            for t in 0..=n {
+                let p = point(p_start, p_step, t);
+                let q = point(q_start, q_step, t);
+                self.glue(
+                    IntersectionOrEdge::Intersection(p.clone()),
+                    IntersectionOrEdge::Intersection(q.clone()),
+                );
+                if t < n {
+                    let p_next = point(p_start, p_step, t + 1);
+                    let q_next = point(q_start, q_step, t + 1);
+                    self.glue(
+                        IntersectionOrEdge::Edge(Self::edge_between(&p, &p_next)),
+                        IntersectionOrEdge::Edge(Self::edge_between(&q, &q_next)),
+                    );
+                }
+            }
+        }
+    }
+
+    fn edge_between(a: &Pos, b: &Pos) -> Edge {
+        if a.y == b.y {
+            Edge {
+                pos: Pos {
+                    x: a.x.min(b.x),
+                    y: a.y,
+                },
+                dir: EdgeDirection::Horizontal,
+            }
+        } else {
+            Edge {
+                pos: Pos {
+                    x: a.x,
+                    y: a.y.min(b.y),
+                },
+                dir: EdgeDirection::Vertical,
+            }
+        }
+    }
+
+    /// Joins two intersections or edges so that both stand for the same node
+    fn glue(&mut self, a: IntersectionOrEdge, b: IntersectionOrEdge) {
+        let a = self.canonical(&a);
+        let b = self.canonical(&b);
+        if a == b {
+            return;
+        }
+        for (_, canonical) in self.seams.iter_mut() {
+            if *canonical == b {
+                *canonical = a.clone();
+            }
+        }
+        self.seams.push((b, a));
+    }
+
+    fn add_missing(&mut self, missing: &[IntersectionOrEdge], missing_cells: &[Pos]) {
+        for m in missing {
+            self.node_mut(m).missing = true;
+            if let IntersectionOrEdge::Intersection(pos) = m {
+                for edge in self.adjacent_edges(pos) {
+                    self.edge_mut(&edge).missing = true;
+                }
+            }
+        }
+        for pos in missing_cells {
+            self.cell_mut(pos).missing = true;
+        }
+    }
+
+    fn add_sources(&mut self, sources: &Vec<IntersectionOrEdge>) {
+        for s in sources {
+            self.node_mut(s).source = true;
+        }
+    }
+
+    fn add_exits(&mut self, exits: &Vec<IntersectionOrEdge>) {
+        for e in exits {
+            if let IntersectionOrEdge::ExitStub(stub) = e {
+                assert_ne!(
+                    self.topology,
+                    Topology::Cube,
+                    "A cube has no border for exit stubs"
+                );
+                let points_outwards = match stub.dir {
+                    Direction::Up => stub.pos.y == 0,
+                    Direction::Down => stub.pos.y == self.height,
+                    Direction::Left => stub.pos.x == 0 && self.topology == Topology::Flat,
+                    Direction::Right => stub.pos.x == self.width && self.topology == Topology::Flat,
+                };
+                assert!(points_outwards, "Exit stub {stub:?} points into the grid");
+                self.exit_stubs
+                    .push((stub.clone(), Node::new(self.ctx, self.num_lines)));
+            }
+            self.node_mut(e).exit = true;
+        }
+    }
+
+    fn add_dots(&mut self, dots: &Vec<IntersectionOrEdge>) {
+        for d in dots {
+            self.node_mut(d).dot = true;
+        }
+    }
+
+    fn add_line_dots(&mut self, dots: &[puzzle::LineDot]) {
+        for d in dots {
+            let line = match d.line {
+                LineColour::Blue => 0,
+                LineColour::Yellow => 1,
+            };
+            assert!(
+                d.line == LineColour::Blue || self.symmetry.is_some(),
+                "Yellow dots need a symmetry puzzle"
+            );
+            let node = self.node_mut(&d.pos);
+            node.dot = true;
+            node.dot_line = Some(line);
+        }
+    }
+
+    fn apparent_colour(&self, colour: Colour) -> Colour {
+        self.colour_filter
+            .iter()
+            .find(|(from, _)| *from == colour)
+            .map(|(_, to)| *to)
+            .unwrap_or(colour)
+    }
+
+    fn set_symbol(&mut self, pos: &Pos, symbol: Symbol) {
+        let pos = match self.lattice {
+            Lattice::Hexagonal => Pos {
+                x: Self::hexagon_start(pos.x, pos.y),
+                y: pos.y,
+            },
+            Lattice::Square | Lattice::Triangular => pos.clone(),
+        };
+        let cell = self.cell_mut(&pos);
+        assert!(!cell.missing, "Symbol on missing cell {pos:?}");
+        cell.symbol = Some(symbol);
+    }
+
+    fn add_squares(&mut self, squares: &[puzzle::ColouredSymbol]) {
+        for s in squares {
+            self.set_symbol(&s.pos, Symbol::Square(self.apparent_colour(s.colour)));
+        }
+    }
+
+    fn add_suns(&mut self, suns: &[puzzle::ColouredSymbol]) {
+        for s in suns {
+            self.set_symbol(&s.pos, Symbol::Sun(self.apparent_colour(s.colour)));
+        }
+    }
+
+    fn add_polyominoes(&mut self, polyominoes: &[puzzle::Polyomino], negative: bool) {
+        for p in polyominoes {
+            let colour = self.apparent_colour(p.colour);
+            let symbol = if negative {
+                Symbol::NegativePolyomino(colour)
+            } else {
+                Symbol::Polyomino(colour)
+            };
+            self.set_symbol(&p.pos, symbol);
+            let polyomino = Polyomino {
+                pos: p.pos.clone(),
+                negative,
+                area: p.shape.len(),
+                placements: self.placements(&p.shape, p.rotatable),
+                placement: z3::ast::Int::fresh_const(self.ctx, "placement"),
+                cancelled: z3::ast::Bool::fresh_const(self.ctx, "cancelled"),
+            };
+            self.polyominoes.push(polyomino);
+        }
+    }
+
+    fn add_eliminations(&mut self, eliminations: &[puzzle::ColouredSymbol]) {
+        for e in eliminations {
+            self.set_symbol(&e.pos, Symbol::Elimination(self.apparent_colour(e.colour)));
+        }
+    }
+
+    fn add_triangles(&mut self, triangles: &[puzzle::Triangle]) {
+        for t in triangles {
+            self.set_symbol(
+                &t.pos,
+                Symbol::Triangle(self.apparent_colour(t.colour), t.count),
+            );
+        }
+    }
+
+    fn add_arrows(&mut self, arrows: &[puzzle::Arrow]) {
+        for a in arrows {
+            self.set_symbol(
+                &a.pos,
+                Symbol::Arrow(self.apparent_colour(a.colour), a.dir, a.count),
+            );
+        }
+    }
+
+    fn add_numbers(&mut self, numbers: &[puzzle::Number]) {
+        for n in numbers {
+            self.set_symbol(
+                &n.pos,
+                Symbol::Number(self.apparent_colour(n.colour), n.count),
+            );
+        }
+    }
+
+    fn placements(&self, shape: &[Pos], rotatable: bool) -> Vec<PolyominoPlacement> {
+        let mut result = Vec::new();
+        for (rotation, shape) in Self::rotations(shape, rotatable) {
+            let shape_width = shape.iter().map(|pos| pos.x + 1).max().unwrap_or(0);
+            let shape_height = shape.iter().map(|pos| pos.y + 1).max().unwrap_or(0);
+            if shape_width > self.width || shape_height > self.height {
+                continue;
+            }
+            // Pieces may be placed across the seam of a cylinder
+            let x_offsets = match self.topology {
+                Topology::Flat | Topology::Cube => self.width - shape_width + 1,
+                Topology::Cylinder => self.width,
+            };
+            for x in 0..x_offsets {
+                for y in 0..(self.height - shape_height + 1) {
+                    result.push(PolyominoPlacement {
+                        rotation,
+                        cells: shape
+                            .iter()
+                            .map(|pos| Pos {
+                                x: (pos.x + x) % self.width,
+                                y: pos.y + y,
+                            })
+                            .collect(),
+                    });
+                }
+            }
+        }
+        result.retain(|placement| placement.cells.iter().all(|pos| !self.cell(pos).missing));
+        result
+    }
+
+    /// Distinct clockwise quarter turns of a shape, each moved to touch the top left corner
+    fn rotations(shape: &[Pos], rotatable: bool) -> Vec<(u32, Vec<Pos>)> {
+        let mut result: Vec<(u32, Vec<Pos>)> = Vec::new();
+        let mut rotated: Vec<(i64, i64)> = shape
+            .iter()
+            .map(|pos| (pos.x as i64, pos.y as i64))
+            .collect();
+        for rotation in 0..(if rotatable { 4 } else { 1 }) {
+            let min_x = rotated.iter().map(|(x, _)| *x).min().unwrap_or(0);
+            let min_y = rotated.iter().map(|(_, y)| *y).min().unwrap_or(0);
+            let normalised: Vec<_> = rotated
+                .iter()
+                .map(|(x, y)| Pos {
+                    x: (x - min_x) as u32,
+                    y: (y - min_y) as u32,
+                })
+                .collect();
+            if !result
+                .iter()
+                .any(|(_, existing)| normalised.iter().all(|pos| existing.contains(pos)))
+            {
+                result.push((rotation, normalised));
+            }
+            rotated = rotated.iter().map(|(x, y)| (-y, *x)).collect();
+        }
+        result
+    }
+
+    fn intersections_and_edges(&self) -> Vec<IntersectionOrEdge> {
+        let mut intersections: Vec<_> = self
+            .intersections()
+            .into_iter()
+            .map(IntersectionOrEdge::Intersection)
+            .collect();
+        let mut edges = self
+            .edges()
+            .into_iter()
+            .map(IntersectionOrEdge::Edge)
+            .collect();
+        intersections.append(&mut edges);
+        intersections.extend(
+            self.exit_stubs
+                .iter()
+                .map(|(stub, _)| IntersectionOrEdge::ExitStub(stub.clone())),
+        );
+        intersections
+    }
+
+    fn intersections(&self) -> Vec<Pos> {
+        let mut result: Vec<Pos> = Vec::new();
+        for x in 0..self.columns() {
+            for y in 0..(self.height + 1) {
+                let pos = Pos { x, y };
+                if !self.is_alias(&IntersectionOrEdge::Intersection(pos.clone())) {
+                    result.push(pos);
+                }
+            }
+        }
+        result
+    }
+
+    fn edges(&self) -> Vec<Edge> {
+        let mut result: Vec<Edge> = Vec::new();
+        for x in 0..self.columns() {
+            for y in 0..(self.height + 1) {
                 if x < self.width {
                     result.push(Edge {
                         pos: Pos { x, y },
@@ -334,61 +1273,106 @@ impl<'ctx> PuzzleModel<'ctx> {
                         dir: EdgeDirection::Vertical,
                     });
                 }
+                if x < self.width && y < self.height {
+                    result.push(Edge {
+                        pos: Pos { x, y },
+                        dir: EdgeDirection::Diagonal,
+                    });
+                }
             }
         }
+        result.retain(|edge| {
+            self.edge_exists(edge) && !self.is_alias(&IntersectionOrEdge::Edge(edge.clone()))
+        });
         result
     }
 
     fn cell_positions(&self) -> Vec<Pos> {
         let mut result: Vec<Pos> = Vec::new();
-        for x in 0..(self.width) {
+        for x in 0..self.cell_columns() {
             for y in 0..(self.height) {
-                result.push(Pos { x, y })
+                if !self.cell(&Pos { x, y }).missing {
+                    result.push(Pos { x, y })
+                }
             }
         }
         result
     }
 
-    fn edges_from_border(&self) -> Vec<Edge> {
-        let mut result: Vec<Edge> = Vec::new();
-        for x in 1..(self.width) {
-            result.push(Edge {
-                dir: EdgeDirection::Vertical,
-                pos: Pos { x, y: 0 },
-            });
-            result.push(Edge {
-                dir: EdgeDirection::Vertical,
-                pos: Pos {
-                    x,
-                    y: self.height - 1,
-                },
-            });
-        }
-        for y in 1..(self.height) {
-            result.push(Edge {
-                dir: EdgeDirection::Horizontal,
-                pos: Pos { x: 0, y },
-            });
-            result.push(Edge {
-                dir: EdgeDirection::Horizontal,
-                pos: Pos {
-                    x: self.width - 1,
-                    y,
-                },
-            });
+    fn cell_index(&self, pos: &Pos) -> u64 {
+        (pos.y * self.cell_columns() + pos.x) as u64
+    }
+
+    fn cell_columns(&self) -> u32 {
+        match self.lattice {
+            Lattice::Triangular => 2 * self.width,
+            Lattice::Square | Lattice::Hexagonal => self.width,
         }
-        result
     }
 
-    fn is_external(&self, intersection_or_edge: &IntersectionOrEdge) -> bool {
-        match intersection_or_edge {
-            IntersectionOrEdge::Intersection(pos) => {
-                pos.x == 0 || pos.x == self.width || pos.y == 0 || pos.y == self.height
+    fn symmetric_counterpart(
+        &self,
+        intersection_or_edge: &IntersectionOrEdge,
+        symmetry: &Symmetry,
+    ) -> IntersectionOrEdge {
+        match (intersection_or_edge, symmetry) {
+            // A half turn is a reflection across both axes
+            (_, Symmetry::Rotational) => {
+                let mirrored =
+                    self.symmetric_counterpart(intersection_or_edge, &Symmetry::Vertical);
+                self.symmetric_counterpart(&mirrored, &Symmetry::Horizontal)
+            }
+            (IntersectionOrEdge::ExitStub(stub), _) => {
+                let pos = match self.symmetric_counterpart(
+                    &IntersectionOrEdge::Intersection(stub.pos.clone()),
+                    symmetry,
+                ) {
+                    IntersectionOrEdge::Intersection(pos) => pos,
+                    _ => unreachable!(),
+                };
+                let dir = match (stub.dir, symmetry) {
+                    (Direction::Left, Symmetry::Vertical) => Direction::Right,
+                    (Direction::Right, Symmetry::Vertical) => Direction::Left,
+                    (Direction::Up, Symmetry::Horizontal) => Direction::Down,
+                    (Direction::Down, Symmetry::Horizontal) => Direction::Up,
+                    (dir, _) => dir,
+                };
+                IntersectionOrEdge::ExitStub(ExitStub { pos, dir })
+            }
+            (IntersectionOrEdge::Intersection(pos), Symmetry::Vertical) => {
+                IntersectionOrEdge::Intersection(Pos {
+                    x: self.width - pos.x,
+                    y: pos.y,
+                })
+            }
+            (IntersectionOrEdge::Intersection(pos), Symmetry::Horizontal) => {
+                IntersectionOrEdge::Intersection(Pos {
+                    x: pos.x,
+                    y: self.height - pos.y,
+                })
+            }
+            (IntersectionOrEdge::Edge(edge), Symmetry::Vertical) => {
+                let x = match edge.dir {
+                    EdgeDirection::Horizontal => self.width - 1 - edge.pos.x,
+                    EdgeDirection::Vertical => self.width - edge.pos.x,
+                    EdgeDirection::Diagonal => unreachable!("Symmetry needs a square lattice"),
+                };
+                IntersectionOrEdge::Edge(Edge {
+                    pos: Pos { x, y: edge.pos.y },
+                    dir: edge.dir.clone(),
+                })
+            }
+            (IntersectionOrEdge::Edge(edge), Symmetry::Horizontal) => {
+                let y = match edge.dir {
+                    EdgeDirection::Horizontal => self.height - edge.pos.y,
+                    EdgeDirection::Vertical => self.height - 1 - edge.pos.y,
+                    EdgeDirection::Diagonal => unreachable!("Symmetry needs a square lattice"),
+                };
+                IntersectionOrEdge::Edge(Edge {
+                    pos: Pos { x: edge.pos.x, y },
+                    dir: edge.dir.clone(),
+                })
             }
-            IntersectionOrEdge::Edge(edge) => match edge.dir {
-                EdgeDirection::Vertical => edge.pos.x == 0 || edge.pos.x == self.width,
-                EdgeDirection::Horizontal => edge.pos.y == 0 || edge.pos.y == self.height,
-            },
         }
     }
 
@@ -406,7 +1390,10 @@ impl<'ctx> PuzzleModel<'ctx> {
             self.constrain_intersection_or_edge(solver, &intersection_or_edge);
         }
         self.constrain_sources_and_exits(solver, &sources, &exits);
-        self.constrain_regions(solver, &sources, &exits);
+        self.constrain_length(solver, &sources, &exits);
+        self.constrain_symmetry(solver);
+        self.constrain_waypoints(solver);
+        self.constrain_regions(solver);
         self.constrain_symbols(solver);
     }
 
@@ -416,20 +1403,60 @@ impl<'ctx> PuzzleModel<'ctx> {
         intersection_or_edge: &IntersectionOrEdge,
     ) {
         let node = self.node(intersection_or_edge);
-        if node.broken {
+        if node.broken || node.missing {
             solver.assert(&!&node.has_line);
         }
+        if node.dot {
+            let has_line = match node.dot_line {
+                Some(line) => &node.lines[line].has_line,
+                None => &node.has_line,
+            };
+            solver.assert(&node.eliminated.not().implies(has_line));
+        }
+
+        // Lines may not touch each other
+        let lines_here = node
+            .lines
+            .iter()
+            .map(|line| (&line.has_line, 1))
+            .collect::<Vec<_>>();
+        solver.assert(&z3::ast::Bool::pb_le(self.ctx, &lines_here, 1));
+        solver.assert(
+            &node
+                .has_line
+                ._eq(&z3::ast::Bool::pb_eq(self.ctx, &lines_here, 1)),
+        );
+
+        for line in 0..self.num_lines {
+            self.constrain_line(solver, intersection_or_edge, line);
+        }
+    }
+
+    fn constrain_line(
+        &self,
+        solver: &z3::Solver,
+        intersection_or_edge: &IntersectionOrEdge,
+        line: usize,
+    ) {
+        let node = self.node(intersection_or_edge);
         if !node.source {
-            solver.assert(&!&node.source_used);
+            solver.assert(&!&node.lines[line].source_used);
         }
         if !node.exit {
-            solver.assert(&!&node.exit_used);
-        }
-        if node.dot {
-            solver.assert(&node.has_line);
+            solver.assert(&!&node.lines[line].exit_used);
         }
+        let node = &node.lines[line];
+
+        // Number the line from its source so that indices increase along it
+        let zero = z3::ast::Int::from_i64(self.ctx, 0);
+        solver.assert(&node.source_used.implies(&node.line_index._eq(&zero)));
+        solver.assert(&node.has_line.implies(&node.line_index.ge(&zero)));
 
-        let adjacent_nodes = self.adjacent_nodes(intersection_or_edge);
+        let adjacent_nodes = self
+            .adjacent_nodes(intersection_or_edge)
+            .into_iter()
+            .map(|adj| &adj.lines[line])
+            .collect::<Vec<_>>();
         let adjacent_nodes_with_line = adjacent_nodes
             .iter()
             .map(|adj| (&adj.has_line, 1))
@@ -472,42 +1499,123 @@ impl<'ctx> PuzzleModel<'ctx> {
         sources: &[IntersectionOrEdge],
         exits: &[IntersectionOrEdge],
     ) {
-        solver.assert(&z3::ast::Bool::pb_eq(
-            self.ctx,
-            &sources
-                .iter()
-                .map(|source| (&self.node(source).source_used, 1))
-                .collect::<Vec<_>>(),
-            1,
-        ));
-        solver.assert(&z3::ast::Bool::pb_eq(
-            self.ctx,
-            &exits
-                .iter()
-                .map(|exit| (&self.node(exit).exit_used, 1))
-                .collect::<Vec<_>>(),
-            1,
-        ));
+        for line in 0..self.num_lines {
+            solver.assert(&z3::ast::Bool::pb_eq(
+                self.ctx,
+                &sources
+                    .iter()
+                    .map(|source| (&self.node(source).lines[line].source_used, 1))
+                    .collect::<Vec<_>>(),
+                1,
+            ));
+            solver.assert(&z3::ast::Bool::pb_eq(
+                self.ctx,
+                &exits
+                    .iter()
+                    .map(|exit| (&self.node(exit).lines[line].exit_used, 1))
+                    .collect::<Vec<_>>(),
+                1,
+            ));
+        }
     }
 
-    fn constrain_regions(
+    fn constrain_length(
         &self,
         solver: &z3::Solver,
         sources: &[IntersectionOrEdge],
         exits: &[IntersectionOrEdge],
     ) {
-        let zero = z3::ast::Int::from_u64(self.ctx, 0);
-        let one = z3::ast::Int::from_u64(self.ctx, 1);
+        let length = match &self.length {
+            Some(length) => length,
+            None => return,
+        };
+        let zero = z3::ast::Int::from_i64(self.ctx, 0);
+        let one = z3::ast::Int::from_i64(self.ctx, 1);
+        let doubled_bound = |n: &u32| z3::ast::Int::from_u64(self.ctx, 2 * *n as u64);
+        for line in 0..self.num_lines {
+            // Indices alternate between intersections and edges, so twice the number of
+            // segments is the exit's index plus any half segments at either end
+            let source_on_edge = sources
+                .iter()
+                .filter(|source| matches!(source, IntersectionOrEdge::Edge(_)))
+                .map(|source| self.node(source).lines[line].source_used.ite(&one, &zero))
+                .reduce(|accum, num| accum + num)
+                .unwrap_or_else(|| z3::ast::Int::from_i64(self.ctx, 0));
+            for exit in exits {
+                let end_adjustment = match exit {
+                    IntersectionOrEdge::Intersection(_) => 0,
+                    IntersectionOrEdge::Edge(_) => 1,
+                    IntersectionOrEdge::ExitStub(_) => -1,
+                };
+                let node = &self.node(exit).lines[line];
+                let doubled_length = &node.line_index
+                    + &source_on_edge
+                    + z3::ast::Int::from_i64(self.ctx, end_adjustment);
+                let bound = match length {
+                    LengthBound::Exactly(n) => doubled_length._eq(&doubled_bound(n)),
+                    LengthBound::AtMost(n) => doubled_length.le(&doubled_bound(n)),
+                    LengthBound::AtLeast(n) => doubled_length.ge(&doubled_bound(n)),
+                };
+                solver.assert(&node.exit_used.implies(&bound));
+            }
+        }
+    }
 
-        for cell_pos in self.cell_positions() {
-            let cell = self.cell(&cell_pos);
-            let cell_region = &cell.region;
-            solver.assert(&(cell_region.ge(&zero)));
-            solver.assert(&(cell_region.lt(&self.num_regions)));
+    fn constrain_symmetry(&self, solver: &z3::Solver) {
+        let symmetry = match &self.symmetry {
+            Some(symmetry) => symmetry,
+            None => return,
+        };
+        // The second line is the first one mirrored
+        for intersection_or_edge in self.intersections_and_edges() {
+            let counterpart = self.symmetric_counterpart(&intersection_or_edge, symmetry);
+            if let IntersectionOrEdge::ExitStub(stub) = &counterpart {
+                // Neither line can leave through a stub whose mirror image is not there
+                if !self.exit_stubs.iter().any(|(other, _)| other == stub) {
+                    for line in &self.node(&intersection_or_edge).lines {
+                        solver.assert(&line.has_line.not());
+                    }
+                    continue;
+                }
+            }
+            let node = &self.node(&intersection_or_edge).lines[0];
+            let counterpart = &self.node(&counterpart).lines[1];
+            solver.assert(&node.has_line._eq(&counterpart.has_line));
+            solver.assert(&node.line_index._eq(&counterpart.line_index));
+            solver.assert(&node.source_used._eq(&counterpart.source_used));
+            solver.assert(&node.exit_used._eq(&counterpart.exit_used));
+        }
+    }
+
+    fn constrain_waypoints(&self, solver: &z3::Solver) {
+        for waypoint in &self.waypoints {
+            solver.assert(&self.node(waypoint).has_line);
         }
+        for pair in self.waypoints.windows(2) {
+            let (first, second) = (self.node(&pair[0]), self.node(&pair[1]));
+            let in_order = first
+                .lines
+                .iter()
+                .zip(second.lines.iter())
+                .map(|(first, second)| {
+                    &first.has_line & &second.has_line & first.line_index.lt(&second.line_index)
+                })
+                .reduce(|acc, condition| acc | condition)
+                .unwrap_or_else(|| z3::ast::Bool::from_bool(self.ctx, false));
+            solver.assert(&in_order);
+        }
+    }
+
+    fn constrain_regions(&self, solver: &z3::Solver) {
+        let zero = z3::ast::Int::from_u64(self.ctx, 0);
+        let num_cells = self.cell_columns() * self.height;
+        let mut neighbours: Vec<Vec<(Edge, Pos)>> = vec![Vec::new(); num_cells as usize];
+        let num_cells = z3::ast::Int::from_u64(self.ctx, num_cells as u64);
+
         for edge in self.edges() {
             let adj_cells = self.adjacent_cells(&edge);
-            if adj_cells.len() == 2 {
+            // Cells on either side of a missing edge are not joined
+            if adj_cells.len() == 2 && adj_cells[0] != adj_cells[1] && !self.edge(&edge).missing {
                 let region_a = &self.cell(&adj_cells[0]).region;
                 let region_b = &self.cell(&adj_cells[1]).region;
                 solver.assert(
@@ -517,71 +1625,64 @@ impl<'ctx> PuzzleModel<'ctx> {
                         .not()
                         .implies(&region_a._eq(region_b)),
                 );
+                neighbours[self.cell_index(&adj_cells[0]) as usize]
+                    .push((edge.clone(), adj_cells[1].clone()));
+                neighbours[self.cell_index(&adj_cells[1]) as usize]
+                    .push((edge.clone(), adj_cells[0].clone()));
             }
         }
-        let region = z3::ast::Int::fresh_const(self.ctx, "region");
-        let valid_region = &region.ge(&zero) & &region.lt(&self.num_regions);
-        let cells_in_region: Vec<_> = self
-            .cell_positions()
-            .iter()
-            .map(|pos| self.cell(pos).region._eq(&region))
-            .collect();
-        solver.assert(&z3::ast::forall_const(
-            self.ctx,
-            &[&region],
-            &[],
-            &valid_region.implies(&z3::ast::Bool::pb_ge(
-                self.ctx,
-                cells_in_region
-                    .iter()
-                    .map(|cond| (cond, 1))
-                    .collect::<Vec<_>>()
-                    .as_ref(),
-                1,
-            )),
-        ));
 
-        let internal_terminations = sources
-            .iter()
-            .chain(exits.iter())
-            .filter(|intersection_or_edge| !self.is_external(intersection_or_edge))
-            .map(|intersection_or_edge| self.node(intersection_or_edge))
-            .map(|node| (&node.source_used ^ &node.exit_used).ite(&one, &zero))
-            .reduce(|accum, num| accum + num)
-            .unwrap_or_else(|| z3::ast::Int::from_i64(self.ctx, 0));
-        let edge_lines_from_border = self
-            .edges_from_border()
-            .iter()
-            .map(|edge| self.edge(edge).has_line.ite(&one, &zero))
-            .reduce(|accum, num| accum + num)
-            .unwrap_or_else(|| z3::ast::Int::from_i64(self.ctx, 0));
-        let mut num_regions = (edge_lines_from_border - internal_terminations)
-            .div(&z3::ast::Int::from_i64(self.ctx, 2))
-            + &one;
-        num_regions = num_regions.le(&zero).ite(&one, &num_regions);
-        if self.width > 0 && self.height > 0 {
-            solver.assert(&self.num_regions._eq(&num_regions));
-        };
+        // Each region is labelled with the index of one of its cells, its root. Every other
+        // cell is connected to the root through a neighbour closer to it, so cells which are
+        // separated by the line cannot share a label.
+        for cell_pos in self.cell_positions() {
+            let cell = self.cell(&cell_pos);
+            solver.assert(&cell.region.ge(&zero));
+            solver.assert(&cell.region.lt(&num_cells));
+            solver.assert(&cell.region_distance.ge(&zero));
+            let is_root = cell.region._eq(&z3::ast::Int::from_u64(
+                self.ctx,
+                self.cell_index(&cell_pos),
+            ));
+            let has_parent = neighbours[self.cell_index(&cell_pos) as usize]
+                .iter()
+                .map(|(edge, other_pos)| {
+                    let other = self.cell(other_pos);
+                    !&self.edge(edge).has_line
+                        & other.region._eq(&cell.region)
+                        & other.region_distance.lt(&cell.region_distance)
+                })
+                .reduce(|acc, condition| acc | condition)
+                .unwrap_or_else(|| z3::ast::Bool::from_bool(self.ctx, false));
+            solver.assert(&is_root.ite(&cell.region_distance._eq(&zero), &has_parent));
+        }
     }
 
     fn constrain_symbols(&self, solver: &z3::Solver) {
-        let coloured_symbol_regions: Vec<_> = self
+        let coloured_symbols: Vec<_> = self
             .cell_positions()
             .iter()
             .map(|pos| self.cell(pos))
             .filter_map(|cell| match &cell.symbol {
                 Some(symbol) => match symbol {
-                    Symbol::Square(colour) | Symbol::Sun(colour) => Some((colour, &cell.region)),
+                    Symbol::Square(colour)
+                    | Symbol::Sun(colour)
+                    | Symbol::Polyomino(colour)
+                    | Symbol::NegativePolyomino(colour)
+                    | Symbol::Elimination(colour)
+                    | Symbol::Triangle(colour, _)
+                    | Symbol::Arrow(colour, _, _)
+                    | Symbol::Number(colour, _) => Some((colour, cell)),
                 },
                 None => None,
             })
             .collect();
-        let square_regions: Vec<_> = self
+        let squares: Vec<_> = self
             .cell_positions()
             .iter()
             .map(|pos| self.cell(pos))
             .filter_map(|cell| match &cell.symbol {
-                Some(Symbol::Square(colour)) => Some((colour, &cell.region)),
+                Some(Symbol::Square(colour)) => Some((colour, cell)),
                 _ => None,
             })
             .collect();
@@ -589,38 +1690,284 @@ impl<'ctx> PuzzleModel<'ctx> {
             let cell = self.cell(&pos);
             match &cell.symbol {
                 Some(symbol) => match symbol {
-                    Symbol::Square(square_colour) => square_regions
+                    Symbol::Square(square_colour) => squares
                         .iter()
                         .filter(|(colour, _)| *colour != square_colour)
-                        .for_each(|(_, region)| solver.assert(&region._eq(&cell.region).not())),
+                        .for_each(|(_, other)| {
+                            let neither_eliminated = !&cell.eliminated & !&other.eliminated;
+                            solver.assert(
+                                &neither_eliminated.implies(&other.region._eq(&cell.region).not()),
+                            )
+                        }),
                     Symbol::Sun(sun_colour) => {
-                        let same_colour_and_region = coloured_symbol_regions
+                        let same_colour_and_region = coloured_symbols
+                            .iter()
+                            .filter(|(colour, _)| colour == &sun_colour)
+                            .map(|(_, other)| !&other.eliminated & other.region._eq(&cell.region))
+                            .collect::<Vec<_>>();
+                        solver.assert(
+                            &cell.eliminated.not().implies(&z3::ast::Bool::pb_eq(
+                                self.ctx,
+                                same_colour_and_region
+                                    .iter()
+                                    .map(|cond| (cond, 1))
+                                    .collect::<Vec<_>>()
+                                    .as_ref(),
+                                2,
+                            )),
+                        );
+                    }
+                    Symbol::Triangle(_, count) => {
+                        let edges = self.cell_edges(&pos);
+                        let edges_with_line = edges
                             .iter()
-                            .filter(|(colour, _region)| colour == &sun_colour)
-                            .map(|(_colour, region)| region._eq(&cell.region))
+                            .map(|edge| (&self.edge(edge).has_line, 1))
                             .collect::<Vec<_>>();
-                        solver.assert(&z3::ast::Bool::pb_eq(
+                        solver.assert(&cell.eliminated.not().implies(&z3::ast::Bool::pb_eq(
                             self.ctx,
-                            same_colour_and_region
-                                .iter()
-                                .map(|cond| (cond, 1))
-                                .collect::<Vec<_>>()
-                                .as_ref(),
-                            2,
-                        ));
+                            &edges_with_line,
+                            *count as i32,
+                        )));
                     }
+                    Symbol::Arrow(_, dir, count) => {
+                        let crossings = self
+                            .arrow_ray(&pos, *dir)
+                            .iter()
+                            .map(|intersection_or_edge| match intersection_or_edge {
+                                IntersectionOrEdge::Intersection(intersection) => {
+                                    self.diagonal_crossing(intersection, *dir)
+                                }
+                                _ => self.node(intersection_or_edge).has_line.clone(),
+                            })
+                            .collect::<Vec<_>>();
+                        solver.assert(
+                            &cell.eliminated.not().implies(&z3::ast::Bool::pb_eq(
+                                self.ctx,
+                                &crossings
+                                    .iter()
+                                    .map(|crossing| (crossing, 1))
+                                    .collect::<Vec<_>>(),
+                                *count as i32,
+                            )),
+                        );
+                    }
+                    Symbol::Number(_, count) => {
+                        let same_region = self
+                            .cell_positions()
+                            .iter()
+                            .map(|other| self.cell(other).region._eq(&cell.region))
+                            .collect::<Vec<_>>();
+                        solver.assert(
+                            &cell.eliminated.not().implies(&z3::ast::Bool::pb_eq(
+                                self.ctx,
+                                same_region
+                                    .iter()
+                                    .map(|cond| (cond, 1))
+                                    .collect::<Vec<_>>()
+                                    .as_ref(),
+                                *count as i32,
+                            )),
+                        );
+                    }
+                    Symbol::Polyomino(_)
+                    | Symbol::NegativePolyomino(_)
+                    | Symbol::Elimination(_) => {}
                 },
                 None => {}
             }
         }
+        self.constrain_polyominoes(solver);
+        self.constrain_eliminations(solver);
+    }
+
+    fn constrain_polyominoes(&self, solver: &z3::Solver) {
+        let zero = z3::ast::Int::from_i64(self.ctx, 0);
+        let one = z3::ast::Int::from_i64(self.ctx, 1);
+        let cell_positions = self.cell_positions();
+        let sign = |polyomino: &Polyomino| if polyomino.negative { -1 } else { 1 };
+
+        let covered_cells: Vec<Vec<_>> = self
+            .polyominoes
+            .iter()
+            .map(|polyomino| {
+                cell_positions
+                    .iter()
+                    .map(|pos| {
+                        polyomino
+                            .placements
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, placement)| placement.cells.contains(pos))
+                            .map(|(i, _)| {
+                                polyomino
+                                    .placement
+                                    ._eq(&z3::ast::Int::from_u64(self.ctx, i as u64))
+                            })
+                            .reduce(|acc, placed| acc | placed)
+                            .unwrap_or_else(|| z3::ast::Bool::from_bool(self.ctx, false))
+                    })
+                    .collect()
+            })
+            .collect();
+
+        for polyomino in &self.polyominoes {
+            let region = &self.cell(&polyomino.pos).region;
+            let same_region: Vec<_> = self
+                .polyominoes
+                .iter()
+                .map(|other| {
+                    let other_cell = self.cell(&other.pos);
+                    !&other_cell.eliminated & other_cell.region._eq(region)
+                })
+                .collect();
+
+            // A region is valid regardless of shape when its negative polyominoes cancel out
+            // the entire area of its positive ones
+            let net_area = self
+                .polyominoes
+                .iter()
+                .zip(same_region.iter())
+                .map(|(other, same)| {
+                    let area = z3::ast::Int::from_i64(self.ctx, sign(other) * other.area as i64);
+                    same.ite(&area, &zero)
+                })
+                .reduce(|accum, num| accum + num)
+                .unwrap_or_else(|| z3::ast::Int::from_i64(self.ctx, 0));
+            solver.assert(&polyomino.cancelled._eq(&net_area._eq(&zero)));
+            let constrained = !&self.cell(&polyomino.pos).eliminated & !&polyomino.cancelled;
+
+            let num_placements =
+                z3::ast::Int::from_u64(self.ctx, polyomino.placements.len() as u64);
+            let valid_placement =
+                &polyomino.placement.ge(&zero) & &polyomino.placement.lt(&num_placements);
+            solver.assert(&constrained.implies(&valid_placement));
+
+            // Otherwise every cell in the region must be covered exactly once after subtracting
+            // negative polyominoes, and any cell outside the region must cancel out to nothing.
+            // Pieces may overlap each other and leave the region as long as this holds
+            for (i, pos) in cell_positions.iter().enumerate() {
+                let coverage = self
+                    .polyominoes
+                    .iter()
+                    .zip(same_region.iter())
+                    .zip(covered_cells.iter())
+                    .map(|((other, same), covered)| {
+                        let count = z3::ast::Int::from_i64(self.ctx, sign(other));
+                        (same & &covered[i]).ite(&count, &zero)
+                    })
+                    .reduce(|accum, num| accum + num)
+                    .unwrap_or_else(|| z3::ast::Int::from_i64(self.ctx, 0));
+                let expected = self.cell(pos).region._eq(region).ite(&one, &zero);
+                solver.assert(&constrained.implies(&coverage._eq(&expected)));
+            }
+        }
     }
 
-    fn extract_line(&self, model: &z3::Model) -> Vec<IntersectionOrEdge> {
+    fn constrain_eliminations(&self, solver: &z3::Solver) {
+        let symbols: Vec<_> = self
+            .cell_positions()
+            .into_iter()
+            .filter(|pos| self.cell(pos).symbol.is_some())
+            .collect();
+        let dots: Vec<_> = self
+            .intersections_and_edges()
+            .into_iter()
+            .filter(|intersection_or_edge| self.node(intersection_or_edge).dot)
+            .collect();
+        let mut symbols_eliminated_by = vec![Vec::new(); symbols.len()];
+        let mut dots_eliminated_by = vec![Vec::new(); dots.len()];
+
+        for elimination_pos in &symbols {
+            let elimination = self.cell(elimination_pos);
+            if !matches!(elimination.symbol, Some(Symbol::Elimination(_))) {
+                continue;
+            }
+            let mut targets = Vec::new();
+            for (i, pos) in symbols.iter().enumerate() {
+                if pos == elimination_pos {
+                    continue;
+                }
+                let target = z3::ast::Bool::fresh_const(self.ctx, "eliminates");
+                solver.assert(&target.implies(&self.cell(pos).region._eq(&elimination.region)));
+                symbols_eliminated_by[i].push(target.clone());
+                targets.push(target);
+            }
+            for (i, dot) in dots.iter().enumerate() {
+                let target = z3::ast::Bool::fresh_const(self.ctx, "eliminates");
+                let touches_region = self
+                    .touching_cells(dot)
+                    .iter()
+                    .map(|pos| self.cell(pos).region._eq(&elimination.region))
+                    .reduce(|acc, touches| acc | touches)
+                    .unwrap_or_else(|| z3::ast::Bool::from_bool(self.ctx, false));
+                solver.assert(&target.implies(&touches_region));
+                dots_eliminated_by[i].push(target.clone());
+                targets.push(target);
+            }
+            let targets: Vec<_> = targets.iter().map(|target| (target, 1)).collect();
+            // An elimination mark must cancel exactly one other symbol, unless it is itself
+            // cancelled by another elimination mark
+            solver.assert(&elimination.eliminated.ite(
+                &z3::ast::Bool::pb_eq(self.ctx, &targets, 0),
+                &z3::ast::Bool::pb_eq(self.ctx, &targets, 1),
+            ));
+        }
+
+        for (pos, eliminated_by) in symbols.iter().zip(symbols_eliminated_by.iter()) {
+            self.constrain_eliminated(solver, &self.cell(pos).eliminated, eliminated_by);
+        }
+        for (dot, eliminated_by) in dots.iter().zip(dots_eliminated_by.iter()) {
+            self.constrain_eliminated(solver, &self.node(dot).eliminated, eliminated_by);
+        }
+    }
+
+    fn constrain_eliminated(
+        &self,
+        solver: &z3::Solver,
+        eliminated: &z3::ast::Bool,
+        eliminated_by: &[z3::ast::Bool],
+    ) {
+        let eliminated_by: Vec<_> = eliminated_by.iter().map(|by| (by, 1)).collect();
+        solver.assert(&z3::ast::Bool::pb_le(self.ctx, &eliminated_by, 1));
+        solver.assert(&eliminated._eq(&z3::ast::Bool::pb_eq(self.ctx, &eliminated_by, 1)));
+    }
+
+    fn extract_eliminated_symbols(&self, model: &z3::Model) -> Vec<Pos> {
+        self.cell_positions()
+            .into_iter()
+            .filter(|pos| {
+                let cell = self.cell(pos);
+                cell.symbol.is_some()
+                    && model
+                        .eval(&cell.eliminated, true)
+                        .unwrap()
+                        .as_bool()
+                        .unwrap()
+            })
+            .collect()
+    }
+
+    fn extract_eliminated_dots(&self, model: &z3::Model) -> Vec<IntersectionOrEdge> {
+        self.intersections_and_edges()
+            .into_iter()
+            .filter(|intersection_or_edge| {
+                let node = self.node(intersection_or_edge);
+                node.dot
+                    && model
+                        .eval(&node.eliminated, true)
+                        .unwrap()
+                        .as_bool()
+                        .unwrap()
+            })
+            .collect()
+    }
+
+    fn extract_line(&self, model: &z3::Model, line_number: usize) -> Vec<IntersectionOrEdge> {
         let mut line = Vec::new();
-        let mut current = self.extract_line_start(model);
+        let mut current = self.extract_line_start(model, line_number);
         line.push(current.clone());
         while !model
-            .eval(&self.node(&current).exit_used, true)
+            .eval(&self.node(&current).lines[line_number].exit_used, true)
             .unwrap()
             .as_bool()
             .unwrap()
@@ -633,7 +1980,7 @@ impl<'ctx> PuzzleModel<'ctx> {
                         return false;
                     }
                     if model
-                        .eval(&self.node(adj).has_line, true)
+                        .eval(&self.node(adj).lines[line_number].has_line, true)
                         .unwrap()
                         .as_bool()
                         .unwrap()
@@ -648,12 +1995,43 @@ impl<'ctx> PuzzleModel<'ctx> {
         line
     }
 
-    fn extract_line_start(&self, model: &z3::Model) -> IntersectionOrEdge {
+    fn extract_polyominoes(
+        &self,
+        model: &z3::Model,
+        negative: bool,
+    ) -> Vec<Option<PolyominoPlacement>> {
+        self.polyominoes
+            .iter()
+            .filter(|polyomino| polyomino.negative == negative)
+            .map(|polyomino| {
+                let eliminated = &self.cell(&polyomino.pos).eliminated;
+                if model
+                    .eval(&(eliminated | &polyomino.cancelled), true)
+                    .unwrap()
+                    .as_bool()
+                    .unwrap()
+                {
+                    return None;
+                }
+                let placement = model
+                    .eval(&polyomino.placement, true)
+                    .unwrap()
+                    .as_u64()
+                    .unwrap();
+                Some(polyomino.placements[placement as usize].clone())
+            })
+            .collect()
+    }
+
+    fn extract_line_start(&self, model: &z3::Model, line_number: usize) -> IntersectionOrEdge {
         self.intersections_and_edges()
             .into_iter()
             .find(|intersection_or_edge| {
                 model
-                    .eval(&self.node(intersection_or_edge).source_used, true)
+                    .eval(
+                        &self.node(intersection_or_edge).lines[line_number].source_used,
+                        true,
+                    )
                     .unwrap()
                     .as_bool()
                     .unwrap()
@@ -662,7 +2040,37 @@ impl<'ctx> PuzzleModel<'ctx> {
     }
 }
 
-pub fn solve(puzzle: &puzzle::Puzzle) -> Option<Vec<IntersectionOrEdge>> {
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution {
+    /// One path per line drawn on the puzzle, starting with the line the player draws
+    pub lines: Vec<Vec<IntersectionOrEdge>>,
+    /// Where each of the puzzle's polyominoes was placed, in the order they were given.
+    /// Pieces in a region whose total area cancels out to zero are not placed
+    pub polyominoes: Vec<Option<PolyominoPlacement>>,
+    pub negative_polyominoes: Vec<Option<PolyominoPlacement>>,
+    pub eliminated_symbols: Vec<Pos>,
+    pub eliminated_dots: Vec<IntersectionOrEdge>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HiddenExitSolution {
+    pub lines: Vec<Vec<IntersectionOrEdge>>,
+    /// The exit each line ends at
+    pub exits: Vec<IntersectionOrEdge>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolyominoPlacement {
+    /// Number of clockwise quarter turns applied to the shape
+    pub rotation: u32,
+    pub cells: Vec<Pos>,
+}
+
+pub fn solve(puzzle: &puzzle::Puzzle) -> Option<Vec<Vec<IntersectionOrEdge>>> {
+    solve_detailed(puzzle).map(|solution| solution.lines)
+}
+
+pub fn solve_detailed(puzzle: &puzzle::Puzzle) -> Option<Solution> {
     let cfg = z3::Config::new();
     let ctx = z3::Context::new(&cfg);
     let puzzle_model = PuzzleModel::from_puzzle(puzzle, &ctx);
@@ -671,7 +2079,223 @@ pub fn solve(puzzle: &puzzle::Puzzle) -> Option<Vec<IntersectionOrEdge>> {
     match solver.check() {
         z3::SatResult::Sat => {
             let model = solver.get_model().unwrap();
-            Some(puzzle_model.extract_line(&model))
+            Some(Solution {
+                lines: (0..puzzle_model.num_lines)
+                    .map(|line| puzzle_model.extract_line(&model, line))
+                    .collect(),
+                polyominoes: puzzle_model.extract_polyominoes(&model, false),
+                negative_polyominoes: puzzle_model.extract_polyominoes(&model, true),
+                eliminated_symbols: puzzle_model.extract_eliminated_symbols(&model),
+                eliminated_dots: puzzle_model.extract_eliminated_dots(&model),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Finds lines which solve several puzzles stacked on top of each other at once
+pub fn solve_overlay(puzzles: &[puzzle::Puzzle]) -> Option<Vec<Vec<IntersectionOrEdge>>> {
+    let first = puzzles.first().expect("No puzzles to overlay");
+    assert!(
+        puzzles
+            .iter()
+            .all(|p| p.width == first.width && p.height == first.height),
+        "Overlaid puzzles must have the same dimensions"
+    );
+    let same_items = |a: &[IntersectionOrEdge], b: &[IntersectionOrEdge]| {
+        a.len() == b.len() && a.iter().all(|item| b.contains(item))
+    };
+    assert!(
+        puzzles
+            .iter()
+            .all(|p| same_items(&p.sources, &first.sources)
+                && same_items(&p.exits, &first.exits)
+                && p.topology == first.topology
+                && p.lattice == first.lattice
+                && p.line_count == first.line_count),
+        "Overlaid puzzles must have the same sources, exits, topology, lattice and line count"
+    );
+    let cfg = z3::Config::new();
+    let ctx = z3::Context::new(&cfg);
+    let first_model = PuzzleModel::from_puzzle(first, &ctx);
+    let solver = z3::Solver::new(&ctx);
+    first_model.constrain(&solver);
+    for puzzle in &puzzles[1..] {
+        let mut layer_model = PuzzleModel::from_puzzle(puzzle, &ctx);
+        layer_model.share_lines(&first_model);
+        layer_model.constrain(&solver);
+    }
+    match solver.check() {
+        z3::SatResult::Sat => {
+            let model = solver.get_model().unwrap();
+            Some(
+                (0..first_model.num_lines)
+                    .map(|line| first_model.extract_line(&model, line))
+                    .collect(),
+            )
+        }
+        _ => None,
+    }
+}
+
+/// Every way of choosing one candidate for each uncertain cell, in the order of `uncertain`
+fn candidate_assignments(puzzle: &puzzle::Puzzle) -> Vec<Vec<Candidate>> {
+    assert!(
+        puzzle
+            .uncertain
+            .iter()
+            .all(|cell| !cell.candidates.is_empty()),
+        "Uncertain cells need at least one candidate"
+    );
+    if puzzle.uncertain.is_empty() {
+        return vec![Vec::new()];
+    }
+    puzzle
+        .uncertain
+        .iter()
+        .map(|cell| cell.candidates.clone())
+        .multi_cartesian_product()
+        .collect()
+}
+
+fn with_candidates(puzzle: &puzzle::Puzzle, assignment: &[Candidate]) -> puzzle::Puzzle {
+    let mut puzzle = puzzle.clone();
+    let uncertain = std::mem::take(&mut puzzle.uncertain);
+    for (cell, candidate) in uncertain.iter().zip(assignment) {
+        match candidate {
+            Candidate::Empty => {}
+            Candidate::Square(colour) => puzzle.squares.push(puzzle::ColouredSymbol {
+                pos: cell.pos.clone(),
+                colour: *colour,
+            }),
+            Candidate::Sun(colour) => puzzle.suns.push(puzzle::ColouredSymbol {
+                pos: cell.pos.clone(),
+                colour: *colour,
+            }),
+        }
+    }
+    puzzle
+}
+
+/// Finds lines which solve the puzzle whichever candidate each uncertain cell holds
+pub fn solve_uncertain(puzzle: &puzzle::Puzzle) -> Option<Vec<Vec<IntersectionOrEdge>>> {
+    let puzzles: Vec<_> = candidate_assignments(puzzle)
+        .iter()
+        .map(|assignment| with_candidates(puzzle, assignment))
+        .collect();
+    solve_overlay(&puzzles)
+}
+
+/// Lists the choices of candidates for the uncertain cells under which the puzzle can be solved
+pub fn solvable_assignments(puzzle: &puzzle::Puzzle) -> Vec<Vec<Candidate>> {
+    candidate_assignments(puzzle)
+        .into_iter()
+        .filter(|assignment| solve(&with_candidates(puzzle, assignment)).is_some())
+        .collect()
+}
+
+/// Builds a model in which any border intersection can be an exit, in place of the puzzle's own
+fn hidden_exit_model<'ctx>(
+    puzzle: &puzzle::Puzzle,
+    ctx: &'ctx z3::Context,
+) -> (PuzzleModel<'ctx>, Vec<IntersectionOrEdge>) {
+    let puzzle = puzzle::Puzzle {
+        exits: Vec::new(),
+        ..puzzle.clone()
+    };
+    let mut puzzle_model = PuzzleModel::from_puzzle(&puzzle, ctx);
+    let exits = puzzle_model.border_intersections();
+    puzzle_model.add_exits(&exits);
+    (puzzle_model, exits)
+}
+
+/// Solves a puzzle whose exits are hidden somewhere on its border
+pub fn solve_hidden_exit(puzzle: &puzzle::Puzzle) -> Option<HiddenExitSolution> {
+    let cfg = z3::Config::new();
+    let ctx = z3::Context::new(&cfg);
+    let (puzzle_model, _) = hidden_exit_model(puzzle, &ctx);
+    let solver = z3::Solver::new(&ctx);
+    puzzle_model.constrain(&solver);
+    match solver.check() {
+        z3::SatResult::Sat => {
+            let model = solver.get_model().unwrap();
+            let lines: Vec<_> = (0..puzzle_model.num_lines)
+                .map(|line| puzzle_model.extract_line(&model, line))
+                .collect();
+            let exits = lines
+                .iter()
+                .filter_map(|line| line.last().cloned())
+                .collect();
+            Some(HiddenExitSolution { lines, exits })
+        }
+        _ => None,
+    }
+}
+
+/// Lists the border intersections which some solution of the puzzle uses as an exit
+pub fn possible_exits(puzzle: &puzzle::Puzzle) -> Vec<IntersectionOrEdge> {
+    let cfg = z3::Config::new();
+    let ctx = z3::Context::new(&cfg);
+    let (puzzle_model, exits) = hidden_exit_model(puzzle, &ctx);
+    let solver = z3::Solver::new(&ctx);
+    puzzle_model.constrain(&solver);
+    exits
+        .into_iter()
+        .filter(|exit| {
+            solver.check_assumptions(&[puzzle_model.exit_used(exit)]) == z3::SatResult::Sat
+        })
+        .collect()
+}
+
+/// Solves every panel of a chain at once, returning the path drawn on each of them
+pub fn solve_chain(chain: &puzzle::PanelChain) -> Option<Vec<Vec<IntersectionOrEdge>>> {
+    assert_eq!(
+        chain.links.len() + 1,
+        chain.panels.len(),
+        "Every panel but the last links to the next one"
+    );
+    assert!(
+        chain.panels.iter().all(|panel| panel.line_count == 1),
+        "Chained panels have one line each"
+    );
+    let cfg = z3::Config::new();
+    let ctx = z3::Context::new(&cfg);
+    let solver = z3::Solver::new(&ctx);
+    let models: Vec<_> = chain
+        .panels
+        .iter()
+        .map(|panel| {
+            let puzzle_model = PuzzleModel::from_puzzle(panel, &ctx);
+            puzzle_model.constrain(&solver);
+            puzzle_model
+        })
+        .collect();
+    for (i, links) in chain.links.iter().enumerate() {
+        let (panel, next_panel) = (&chain.panels[i], &chain.panels[i + 1]);
+        let (model, next_model) = (&models[i], &models[i + 1]);
+        for exit in &panel.exits {
+            if !links.iter().any(|(linked, _)| linked == exit) {
+                solver.assert(&model.exit_used(exit).not());
+            }
+        }
+        for source in &next_panel.sources {
+            if !links.iter().any(|(_, linked)| linked == source) {
+                solver.assert(&next_model.source_used(source).not());
+            }
+        }
+        for (exit, source) in links {
+            solver.assert(&model.exit_used(exit)._eq(&next_model.source_used(source)));
+        }
+    }
+    match solver.check() {
+        z3::SatResult::Sat => {
+            let model = solver.get_model().unwrap();
+            Some(
+                models
+                    .iter()
+                    .map(|puzzle_model| puzzle_model.extract_line(&model, 0))
+                    .collect(),
+            )
         }
         _ => None,
     }
@@ -0,0 +1,196 @@
+use witness::{
+    solve, Arrow, ArrowDirection, Colour, Edge, EdgeDirection, IntersectionOrEdge, Pos, Puzzle,
+};
+
+#[test]
+fn arrow_pointing_away_from_line() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        arrows: vec![Arrow {
+            pos: Pos { x: 0, y: 0 },
+            dir: ArrowDirection::Right,
+            count: 0,
+            colour: Colour::Purple,
+        }],
+        ..Default::default()
+    };
+    assert_eq!(
+        solve(&puzzle),
+        Some(vec![vec![
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 0, y: 0 },
+                dir: EdgeDirection::Horizontal,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 0, y: 0 },
+                dir: EdgeDirection::Vertical,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 0, y: 1 },
+                dir: EdgeDirection::Horizontal,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 }),
+        ]])
+    );
+}
+
+#[test]
+fn arrow_count_too_high_unsat() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        arrows: vec![Arrow {
+            pos: Pos { x: 0, y: 0 },
+            dir: ArrowDirection::Right,
+            count: 2,
+            colour: Colour::Purple,
+        }],
+        ..Default::default()
+    };
+    assert_eq!(solve(&puzzle), None);
+}
+
+#[test]
+fn upward_arrow_crossed_twice() {
+    let puzzle = Puzzle {
+        width: 1,
+        height: 2,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 2 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        arrows: vec![Arrow {
+            pos: Pos { x: 0, y: 1 },
+            dir: ArrowDirection::Up,
+            count: 2,
+            colour: Colour::Purple,
+        }],
+        ..Default::default()
+    };
+    assert_eq!(
+        solve(&puzzle),
+        Some(vec![vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 0, y: 0 },
+                dir: EdgeDirection::Horizontal,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 1, y: 0 },
+                dir: EdgeDirection::Vertical,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 0, y: 1 },
+                dir: EdgeDirection::Horizontal,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 0, y: 1 },
+                dir: EdgeDirection::Vertical,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 2 }),
+        ]])
+    );
+}
+
+#[test]
+fn diagonal_arrow_counts_crossings() {
+    let puzzle = Puzzle {
+        width: 1,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        arrows: vec![Arrow {
+            pos: Pos { x: 0, y: 0 },
+            dir: ArrowDirection::DownRight,
+            count: 1,
+            colour: Colour::Purple,
+        }],
+        ..Default::default()
+    };
+    assert_eq!(
+        solve(&puzzle),
+        Some(vec![vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 0, y: 0 },
+                dir: EdgeDirection::Vertical,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 0, y: 1 },
+                dir: EdgeDirection::Horizontal,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 1, y: 0 },
+                dir: EdgeDirection::Vertical,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 }),
+        ]])
+    );
+}
+
+#[test]
+fn diagonal_arrow_ignores_touching_line() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 2,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 2 })],
+        broken: vec![],
+        dots: vec![IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 0, y: 1 },
+            dir: EdgeDirection::Horizontal,
+        })],
+        squares: vec![],
+        suns: vec![],
+        arrows: vec![Arrow {
+            pos: Pos { x: 0, y: 0 },
+            dir: ArrowDirection::DownRight,
+            count: 0,
+            colour: Colour::Purple,
+        }],
+        ..Default::default()
+    };
+    assert_eq!(
+        solve(&puzzle),
+        Some(vec![vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 0, y: 1 },
+                dir: EdgeDirection::Horizontal,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 1, y: 1 },
+                dir: EdgeDirection::Vertical,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 2 }),
+        ]])
+    );
+}
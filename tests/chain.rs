@@ -0,0 +1,124 @@
+use witness::{solve_chain, Edge, EdgeDirection, IntersectionOrEdge, PanelChain, Pos, Puzzle};
+
+#[test]
+fn later_panel_picks_exit() {
+    let first = Puzzle {
+        width: 1,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 }),
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+        ],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        ..Default::default()
+    };
+    let second = Puzzle {
+        width: 1,
+        height: 1,
+        sources: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 }),
+        ],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 })],
+        broken: vec![IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 0, y: 0 },
+            dir: EdgeDirection::Vertical,
+        })],
+        dots: vec![IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 0, y: 0 },
+            dir: EdgeDirection::Horizontal,
+        })],
+        squares: vec![],
+        suns: vec![],
+        ..Default::default()
+    };
+    let chain = PanelChain {
+        panels: vec![first, second],
+        links: vec![vec![
+            (
+                IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 }),
+                IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+            ),
+            (
+                IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+                IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 }),
+            ),
+        ]],
+    };
+    let paths = solve_chain(&chain).unwrap();
+    assert_eq!(
+        paths[0].last(),
+        Some(&IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 }))
+    );
+    assert_eq!(
+        paths[1],
+        vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 0, y: 0 },
+                dir: EdgeDirection::Horizontal,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 1, y: 0 },
+                dir: EdgeDirection::Vertical,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 0, y: 1 },
+                dir: EdgeDirection::Horizontal,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+        ]
+    );
+}
+
+#[test]
+fn later_panel_rules_out_chain() {
+    let first = Puzzle {
+        width: 1,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 }),
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+        ],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        ..Default::default()
+    };
+    let second = Puzzle {
+        width: 1,
+        height: 1,
+        sources: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 }),
+        ],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 })],
+        broken: vec![IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 0, y: 0 },
+            dir: EdgeDirection::Vertical,
+        })],
+        dots: vec![IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 0, y: 0 },
+            dir: EdgeDirection::Horizontal,
+        })],
+        squares: vec![],
+        suns: vec![],
+        ..Default::default()
+    };
+    let chain = PanelChain {
+        panels: vec![first, second],
+        links: vec![vec![(
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 }),
+        )]],
+    };
+    assert_eq!(solve_chain(&chain), None);
+}
@@ -0,0 +1,78 @@
+use witness::{
+    solve, Colour, ColouredSymbol, Edge, EdgeDirection, IntersectionOrEdge, Pos, Puzzle,
+};
+
+fn white_and_green_squares(colour_filter: Vec<(Colour, Colour)>) -> Puzzle {
+    Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 })],
+        broken: vec![IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 1, y: 0 },
+            dir: EdgeDirection::Vertical,
+        })],
+        dots: vec![],
+        squares: vec![
+            ColouredSymbol {
+                pos: Pos { x: 0, y: 0 },
+                colour: Colour::White,
+            },
+            ColouredSymbol {
+                pos: Pos { x: 1, y: 0 },
+                colour: Colour::Green,
+            },
+        ],
+        suns: vec![],
+        colour_filter,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn squares_without_filter_unsat() {
+    assert_eq!(solve(&white_and_green_squares(vec![])), None);
+}
+
+#[test]
+fn filter_merges_square_colours() {
+    let puzzle = white_and_green_squares(vec![(Colour::White, Colour::Green)]);
+    assert!(matches!(solve(&puzzle), Some(_)));
+}
+
+#[test]
+fn filter_matches_named_colour_by_shade() {
+    let puzzle = white_and_green_squares(vec![(Colour::Rgb(255, 255, 255), Colour::Green)]);
+    assert!(matches!(solve(&puzzle), Some(_)));
+}
+
+#[test]
+fn filter_pairs_sun_with_square() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 })],
+        broken: vec![IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 1, y: 0 },
+            dir: EdgeDirection::Vertical,
+        })],
+        dots: vec![],
+        squares: vec![ColouredSymbol {
+            pos: Pos { x: 1, y: 0 },
+            colour: Colour::Orange,
+        }],
+        suns: vec![ColouredSymbol {
+            pos: Pos { x: 0, y: 0 },
+            colour: Colour::Red,
+        }],
+        colour_filter: vec![(Colour::Orange, Colour::Red)],
+        ..Default::default()
+    };
+    assert!(matches!(solve(&puzzle), Some(_)));
+    let unfiltered = Puzzle {
+        colour_filter: vec![],
+        ..puzzle
+    };
+    assert_eq!(solve(&unfiltered), None);
+}
@@ -0,0 +1,203 @@
+use witness::{
+    solve, Arrow, ArrowDirection, Colour, ColouredSymbol, Edge, EdgeDirection, IntersectionOrEdge,
+    Polyomino, Pos, Puzzle, Symmetry, Topology,
+};
+
+#[test]
+fn line_does_not_split_cube() {
+    let puzzle = Puzzle {
+        width: 4,
+        height: 3,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 2, y: 2 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![
+            ColouredSymbol {
+                pos: Pos { x: 1, y: 0 },
+                colour: Colour::Black,
+            },
+            ColouredSymbol {
+                pos: Pos { x: 1, y: 2 },
+                colour: Colour::White,
+            },
+        ],
+        suns: vec![],
+        topology: Topology::Cube,
+        ..Default::default()
+    };
+    assert_eq!(solve(&puzzle), None);
+}
+
+#[test]
+fn dots_on_every_corner_sat() {
+    let puzzle = Puzzle {
+        width: 4,
+        height: 3,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 })],
+        broken: vec![],
+        dots: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 1 }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 2 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 2 }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 3 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 3 }),
+        ],
+        squares: vec![],
+        suns: vec![],
+        topology: Topology::Cube,
+        ..Default::default()
+    };
+    assert!(matches!(solve(&puzzle), Some(_)));
+}
+
+#[test]
+fn dots_on_every_corner_unsat() {
+    let puzzle = Puzzle {
+        width: 4,
+        height: 3,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 2 })],
+        broken: vec![],
+        dots: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 1 }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 2 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 2 }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 3 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 3 }),
+        ],
+        squares: vec![],
+        suns: vec![],
+        topology: Topology::Cube,
+        ..Default::default()
+    };
+    assert_eq!(solve(&puzzle), None);
+}
+
+#[test]
+fn line_crosses_fold() {
+    let puzzle = Puzzle {
+        width: 4,
+        height: 3,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 2, y: 1 })],
+        broken: vec![],
+        dots: vec![IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 0, y: 1 },
+            dir: EdgeDirection::Vertical,
+        })],
+        squares: vec![],
+        suns: vec![],
+        topology: Topology::Cube,
+        ..Default::default()
+    };
+    let lines = solve(&puzzle).unwrap();
+    assert!(lines[0].contains(&IntersectionOrEdge::Edge(Edge {
+        pos: Pos { x: 4, y: 1 },
+        dir: EdgeDirection::Vertical,
+    })));
+}
+
+#[test]
+#[should_panic(expected = "Arrows cannot follow their ray around a cube")]
+fn arrow_on_cube() {
+    let puzzle = Puzzle {
+        width: 4,
+        height: 3,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 2, y: 1 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        arrows: vec![Arrow {
+            pos: Pos { x: 1, y: 1 },
+            dir: ArrowDirection::Right,
+            count: 1,
+            colour: Colour::Black,
+        }],
+        topology: Topology::Cube,
+        ..Default::default()
+    };
+    solve(&puzzle);
+}
+
+#[test]
+#[should_panic(expected = "Polyominoes cannot be placed across the folds of a cube")]
+fn polyomino_on_cube() {
+    let puzzle = Puzzle {
+        width: 4,
+        height: 3,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 2, y: 1 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        polyominoes: vec![Polyomino {
+            pos: Pos { x: 1, y: 1 },
+            shape: vec![Pos { x: 0, y: 0 }, Pos { x: 1, y: 0 }],
+            colour: Colour::Yellow,
+            rotatable: false,
+        }],
+        topology: Topology::Cube,
+        ..Default::default()
+    };
+    solve(&puzzle);
+}
+
+#[test]
+#[should_panic(expected = "Polyominoes cannot be placed across the folds of a cube")]
+fn negative_polyomino_on_cube() {
+    let puzzle = Puzzle {
+        width: 4,
+        height: 3,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 2, y: 1 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        negative_polyominoes: vec![Polyomino {
+            pos: Pos { x: 1, y: 1 },
+            shape: vec![Pos { x: 0, y: 0 }],
+            colour: Colour::Blue,
+            rotatable: false,
+        }],
+        topology: Topology::Cube,
+        ..Default::default()
+    };
+    solve(&puzzle);
+}
+
+#[test]
+#[should_panic(expected = "Only horizontal symmetry maps the net of a cube onto itself")]
+fn vertical_symmetry_on_cube() {
+    let puzzle = Puzzle {
+        width: 4,
+        height: 3,
+        sources: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 }),
+            IntersectionOrEdge::Intersection(Pos { x: 3, y: 1 }),
+        ],
+        exits: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 2 }),
+            IntersectionOrEdge::Intersection(Pos { x: 3, y: 2 }),
+        ],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        symmetry: Some(Symmetry::Vertical),
+        topology: Topology::Cube,
+        ..Default::default()
+    };
+    solve(&puzzle);
+}
@@ -0,0 +1,137 @@
+use witness::{
+    solve, solve_detailed, Colour, ColouredSymbol, Edge, EdgeDirection, IntersectionOrEdge,
+    Polyomino, PolyominoPlacement, Pos, Puzzle, Topology,
+};
+
+#[test]
+fn line_crosses_seam() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        broken: vec![
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 0, y: 0 },
+                dir: EdgeDirection::Horizontal,
+            }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 1, y: 0 },
+                dir: EdgeDirection::Vertical,
+            }),
+        ],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        topology: Topology::Cylinder,
+        ..Default::default()
+    };
+    assert_eq!(
+        solve(&puzzle),
+        Some(vec![vec![
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 1, y: 0 },
+                dir: EdgeDirection::Horizontal,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+        ]])
+    );
+}
+
+#[test]
+fn single_cut_does_not_split_cylinder() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![
+            ColouredSymbol {
+                pos: Pos { x: 0, y: 0 },
+                colour: Colour::Black,
+            },
+            ColouredSymbol {
+                pos: Pos { x: 1, y: 0 },
+                colour: Colour::White,
+            },
+        ],
+        suns: vec![],
+        topology: Topology::Cylinder,
+        ..Default::default()
+    };
+    assert_eq!(solve(&puzzle), None);
+}
+
+#[test]
+fn regions_join_across_seam() {
+    let puzzle = Puzzle {
+        width: 3,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![
+            ColouredSymbol {
+                pos: Pos { x: 0, y: 0 },
+                colour: Colour::Black,
+            },
+            ColouredSymbol {
+                pos: Pos { x: 1, y: 0 },
+                colour: Colour::White,
+            },
+            ColouredSymbol {
+                pos: Pos { x: 2, y: 0 },
+                colour: Colour::Black,
+            },
+        ],
+        suns: vec![],
+        topology: Topology::Cylinder,
+        ..Default::default()
+    };
+    let line = solve(&puzzle).unwrap().remove(0);
+    assert!(line.contains(&IntersectionOrEdge::Edge(Edge {
+        pos: Pos { x: 1, y: 0 },
+        dir: EdgeDirection::Vertical,
+    })));
+    assert!(line.contains(&IntersectionOrEdge::Edge(Edge {
+        pos: Pos { x: 2, y: 0 },
+        dir: EdgeDirection::Vertical,
+    })));
+}
+
+#[test]
+fn polyomino_wraps_around_seam() {
+    let puzzle = Puzzle {
+        width: 3,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 })],
+        broken: vec![IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 0, y: 0 },
+            dir: EdgeDirection::Vertical,
+        })],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        polyominoes: vec![Polyomino {
+            pos: Pos { x: 0, y: 0 },
+            shape: vec![Pos { x: 0, y: 0 }, Pos { x: 1, y: 0 }],
+            colour: Colour::Yellow,
+            rotatable: false,
+        }],
+        topology: Topology::Cylinder,
+        ..Default::default()
+    };
+    let solution = solve_detailed(&puzzle).unwrap();
+    assert_eq!(
+        solution.polyominoes,
+        vec![Some(PolyominoPlacement {
+            rotation: 0,
+            cells: vec![Pos { x: 2, y: 0 }, Pos { x: 0, y: 0 }],
+        })]
+    );
+}
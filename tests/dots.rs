@@ -14,10 +14,11 @@ fn simple_sat() {
         })],
         squares: vec![],
         suns: vec![],
+        ..Default::default()
     };
     assert_eq!(
         solve(&puzzle),
-        Some(vec![
+        Some(vec![vec![
             IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
             IntersectionOrEdge::Edge(Edge {
                 pos: Pos { x: 0, y: 0 },
@@ -29,7 +30,7 @@ fn simple_sat() {
                 dir: EdgeDirection::Horizontal
             }),
             IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })
-        ])
+        ]])
     );
 }
 
@@ -47,6 +48,7 @@ fn dead_end() {
         dots: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 })],
         squares: vec![],
         suns: vec![],
+        ..Default::default()
     };
     assert_eq!(solve(&puzzle), None);
 }
@@ -71,6 +73,7 @@ fn no_loops() {
         dots: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 2 })],
         squares: vec![],
         suns: vec![],
+        ..Default::default()
     };
     assert_eq!(solve(&puzzle), None);
 }
@@ -89,6 +92,7 @@ fn internal_line() {
         })],
         squares: vec![],
         suns: vec![],
+        ..Default::default()
     };
     assert!(matches!(solve(&puzzle), Some(_)));
 }
@@ -0,0 +1,186 @@
+use witness::{
+    solve, solve_detailed, Colour, ColouredSymbol, Edge, EdgeDirection, IntersectionOrEdge, Pos,
+    Puzzle,
+};
+
+#[test]
+fn lone_elimination_unsat() {
+    let puzzle = Puzzle {
+        width: 1,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        eliminations: vec![ColouredSymbol {
+            pos: Pos { x: 0, y: 0 },
+            colour: Colour::White,
+        }],
+        ..Default::default()
+    };
+    assert_eq!(solve(&puzzle), None);
+}
+
+#[test]
+fn squares_without_elimination_unsat() {
+    let puzzle = Puzzle {
+        width: 3,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 3, y: 0 })],
+        broken: vec![
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 1, y: 0 },
+                dir: EdgeDirection::Vertical,
+            }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 2, y: 0 },
+                dir: EdgeDirection::Vertical,
+            }),
+        ],
+        dots: vec![],
+        squares: vec![
+            ColouredSymbol {
+                pos: Pos { x: 0, y: 0 },
+                colour: Colour::Black,
+            },
+            ColouredSymbol {
+                pos: Pos { x: 2, y: 0 },
+                colour: Colour::White,
+            },
+        ],
+        suns: vec![],
+        ..Default::default()
+    };
+    assert_eq!(solve(&puzzle), None);
+}
+
+#[test]
+fn elimination_cancels_square() {
+    let puzzle = Puzzle {
+        width: 3,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 3, y: 0 })],
+        broken: vec![
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 1, y: 0 },
+                dir: EdgeDirection::Vertical,
+            }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 2, y: 0 },
+                dir: EdgeDirection::Vertical,
+            }),
+        ],
+        dots: vec![],
+        squares: vec![
+            ColouredSymbol {
+                pos: Pos { x: 0, y: 0 },
+                colour: Colour::Black,
+            },
+            ColouredSymbol {
+                pos: Pos { x: 2, y: 0 },
+                colour: Colour::White,
+            },
+        ],
+        suns: vec![],
+        eliminations: vec![ColouredSymbol {
+            pos: Pos { x: 1, y: 0 },
+            colour: Colour::White,
+        }],
+        ..Default::default()
+    };
+    let solution = solve_detailed(&puzzle).unwrap();
+    assert_eq!(solution.eliminated_symbols.len(), 1);
+    assert_ne!(solution.eliminated_symbols[0], Pos { x: 1, y: 0 });
+    assert_eq!(solution.eliminated_dots, vec![]);
+}
+
+#[test]
+fn elimination_cancels_dot() {
+    let puzzle = Puzzle {
+        width: 1,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 })],
+        broken: vec![],
+        dots: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 })],
+        squares: vec![],
+        suns: vec![],
+        eliminations: vec![ColouredSymbol {
+            pos: Pos { x: 0, y: 0 },
+            colour: Colour::White,
+        }],
+        ..Default::default()
+    };
+    let solution = solve_detailed(&puzzle).unwrap();
+    assert_eq!(solution.eliminated_symbols, vec![]);
+    assert_eq!(
+        solution.eliminated_dots,
+        vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 })]
+    );
+}
+
+#[test]
+fn eliminations_cancel_each_other() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        eliminations: vec![
+            ColouredSymbol {
+                pos: Pos { x: 0, y: 0 },
+                colour: Colour::White,
+            },
+            ColouredSymbol {
+                pos: Pos { x: 1, y: 0 },
+                colour: Colour::White,
+            },
+        ],
+        ..Default::default()
+    };
+    let solution = solve_detailed(&puzzle).unwrap();
+    assert_eq!(solution.eliminated_symbols.len(), 1);
+}
+
+#[test]
+fn elimination_in_other_region_unsat() {
+    let puzzle = Puzzle {
+        width: 3,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 3, y: 0 })],
+        broken: vec![IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 1, y: 0 },
+            dir: EdgeDirection::Vertical,
+        })],
+        dots: vec![IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 2, y: 0 },
+            dir: EdgeDirection::Vertical,
+        })],
+        squares: vec![
+            ColouredSymbol {
+                pos: Pos { x: 0, y: 0 },
+                colour: Colour::Black,
+            },
+            ColouredSymbol {
+                pos: Pos { x: 1, y: 0 },
+                colour: Colour::White,
+            },
+        ],
+        suns: vec![],
+        eliminations: vec![ColouredSymbol {
+            pos: Pos { x: 2, y: 0 },
+            colour: Colour::White,
+        }],
+        ..Default::default()
+    };
+    assert_eq!(solve(&puzzle), None);
+}
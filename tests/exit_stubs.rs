@@ -0,0 +1,140 @@
+use witness::{
+    solve, Direction, Edge, EdgeDirection, ExitStub, IntersectionOrEdge, Pos, Puzzle, Symmetry,
+};
+
+#[test]
+fn line_ends_in_stub() {
+    let puzzle = Puzzle {
+        width: 1,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::ExitStub(ExitStub {
+            pos: Pos { x: 1, y: 1 },
+            dir: Direction::Right,
+        })],
+        broken: vec![IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 0, y: 0 },
+            dir: EdgeDirection::Horizontal,
+        })],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        ..Default::default()
+    };
+    assert_eq!(
+        solve(&puzzle),
+        Some(vec![vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 0, y: 0 },
+                dir: EdgeDirection::Vertical,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 0, y: 1 },
+                dir: EdgeDirection::Horizontal,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 }),
+            IntersectionOrEdge::ExitStub(ExitStub {
+                pos: Pos { x: 1, y: 1 },
+                dir: Direction::Right,
+            }),
+        ]])
+    );
+}
+
+#[test]
+#[should_panic(expected = "points into the grid")]
+fn stub_pointing_into_grid() {
+    let puzzle = Puzzle {
+        width: 1,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::ExitStub(ExitStub {
+            pos: Pos { x: 0, y: 1 },
+            dir: Direction::Right,
+        })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        ..Default::default()
+    };
+    solve(&puzzle);
+}
+
+#[test]
+fn mirrored_stubs() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 1 }),
+        ],
+        exits: vec![
+            IntersectionOrEdge::ExitStub(ExitStub {
+                pos: Pos { x: 0, y: 0 },
+                dir: Direction::Left,
+            }),
+            IntersectionOrEdge::ExitStub(ExitStub {
+                pos: Pos { x: 2, y: 0 },
+                dir: Direction::Right,
+            }),
+        ],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        symmetry: Some(Symmetry::Vertical),
+        ..Default::default()
+    };
+    let lines = solve(&puzzle).unwrap();
+    assert!(lines.contains(&vec![
+        IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+        IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 0, y: 0 },
+            dir: EdgeDirection::Vertical,
+        }),
+        IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+        IntersectionOrEdge::ExitStub(ExitStub {
+            pos: Pos { x: 0, y: 0 },
+            dir: Direction::Left,
+        }),
+    ]));
+    assert!(lines.contains(&vec![
+        IntersectionOrEdge::Intersection(Pos { x: 2, y: 1 }),
+        IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 2, y: 0 },
+            dir: EdgeDirection::Vertical,
+        }),
+        IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 }),
+        IntersectionOrEdge::ExitStub(ExitStub {
+            pos: Pos { x: 2, y: 0 },
+            dir: Direction::Right,
+        }),
+    ]));
+}
+
+#[test]
+fn stub_without_mirror_image_unsat() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 1 }),
+        ],
+        exits: vec![IntersectionOrEdge::ExitStub(ExitStub {
+            pos: Pos { x: 0, y: 0 },
+            dir: Direction::Left,
+        })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        symmetry: Some(Symmetry::Vertical),
+        ..Default::default()
+    };
+    assert_eq!(solve(&puzzle), None);
+}
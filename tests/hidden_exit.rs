@@ -0,0 +1,123 @@
+use witness::{
+    possible_exits, solve_hidden_exit, Colour, ColouredSymbol, Edge, EdgeDirection,
+    HiddenExitSolution, IntersectionOrEdge, Pos, Puzzle,
+};
+
+#[test]
+fn finds_hidden_exit() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![],
+        broken: vec![
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 0, y: 0 },
+                dir: EdgeDirection::Horizontal,
+            }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 1, y: 0 },
+                dir: EdgeDirection::Horizontal,
+            }),
+        ],
+        dots: vec![],
+        squares: vec![
+            ColouredSymbol {
+                pos: Pos { x: 0, y: 0 },
+                colour: Colour::Black,
+            },
+            ColouredSymbol {
+                pos: Pos { x: 1, y: 0 },
+                colour: Colour::White,
+            },
+        ],
+        suns: vec![],
+        ..Default::default()
+    };
+    assert_eq!(
+        solve_hidden_exit(&puzzle),
+        Some(HiddenExitSolution {
+            lines: vec![vec![
+                IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+                IntersectionOrEdge::Edge(Edge {
+                    pos: Pos { x: 0, y: 0 },
+                    dir: EdgeDirection::Vertical,
+                }),
+                IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+                IntersectionOrEdge::Edge(Edge {
+                    pos: Pos { x: 0, y: 1 },
+                    dir: EdgeDirection::Horizontal,
+                }),
+                IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 }),
+                IntersectionOrEdge::Edge(Edge {
+                    pos: Pos { x: 1, y: 0 },
+                    dir: EdgeDirection::Vertical,
+                }),
+                IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 }),
+            ]],
+            exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 })],
+        })
+    );
+}
+
+#[test]
+fn lists_possible_exits() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![],
+        broken: vec![IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 0, y: 0 },
+            dir: EdgeDirection::Horizontal,
+        })],
+        dots: vec![],
+        squares: vec![
+            ColouredSymbol {
+                pos: Pos { x: 0, y: 0 },
+                colour: Colour::Black,
+            },
+            ColouredSymbol {
+                pos: Pos { x: 1, y: 0 },
+                colour: Colour::White,
+            },
+        ],
+        suns: vec![],
+        ..Default::default()
+    };
+    assert_eq!(
+        possible_exits(&puzzle),
+        vec![
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 1 }),
+        ]
+    );
+}
+
+#[test]
+fn exits_only_on_border() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 2,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        ..Default::default()
+    };
+    assert_eq!(
+        possible_exits(&puzzle),
+        vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 2 }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 2 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 1 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 2 }),
+        ]
+    );
+}
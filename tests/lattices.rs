@@ -0,0 +1,221 @@
+use witness::{
+    solve, Colour, ColouredSymbol, Edge, EdgeDirection, IntersectionOrEdge, Lattice, Pos, Puzzle,
+};
+
+#[test]
+fn line_takes_diagonal() {
+    let puzzle = Puzzle {
+        width: 1,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })],
+        broken: vec![],
+        dots: vec![IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 0, y: 0 },
+            dir: EdgeDirection::Diagonal,
+        })],
+        squares: vec![],
+        suns: vec![],
+        lattice: Lattice::Triangular,
+        ..Default::default()
+    };
+    assert_eq!(
+        solve(&puzzle),
+        Some(vec![vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 0, y: 0 },
+                dir: EdgeDirection::Diagonal,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 }),
+        ]])
+    );
+}
+
+#[test]
+fn diagonal_separates_triangles_sat() {
+    let puzzle = Puzzle {
+        width: 1,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![
+            ColouredSymbol {
+                pos: Pos { x: 0, y: 0 },
+                colour: Colour::Black,
+            },
+            ColouredSymbol {
+                pos: Pos { x: 1, y: 0 },
+                colour: Colour::White,
+            },
+        ],
+        suns: vec![],
+        lattice: Lattice::Triangular,
+        ..Default::default()
+    };
+    assert!(matches!(solve(&puzzle), Some(_)));
+}
+
+#[test]
+fn diagonal_separates_triangles_unsat() {
+    let puzzle = Puzzle {
+        width: 1,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })],
+        broken: vec![IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 0, y: 0 },
+            dir: EdgeDirection::Diagonal,
+        })],
+        dots: vec![],
+        squares: vec![
+            ColouredSymbol {
+                pos: Pos { x: 0, y: 0 },
+                colour: Colour::Black,
+            },
+            ColouredSymbol {
+                pos: Pos { x: 1, y: 0 },
+                colour: Colour::White,
+            },
+        ],
+        suns: vec![],
+        lattice: Lattice::Triangular,
+        ..Default::default()
+    };
+    assert_eq!(solve(&puzzle), None);
+}
+
+#[test]
+fn line_follows_sides_of_hexagon() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        lattice: Lattice::Hexagonal,
+        ..Default::default()
+    };
+    let lines = solve(&puzzle).unwrap();
+    assert_eq!(lines[0].len(), 7);
+    assert!(!lines[0].contains(&IntersectionOrEdge::Edge(Edge {
+        pos: Pos { x: 1, y: 0 },
+        dir: EdgeDirection::Vertical,
+    })));
+}
+
+#[test]
+fn broken_side_joins_hexagons_sat() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 2,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 2 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![
+            ColouredSymbol {
+                pos: Pos { x: 0, y: 1 },
+                colour: Colour::Black,
+            },
+            ColouredSymbol {
+                pos: Pos { x: 1, y: 1 },
+                colour: Colour::White,
+            },
+        ],
+        suns: vec![],
+        lattice: Lattice::Hexagonal,
+        ..Default::default()
+    };
+    assert!(matches!(solve(&puzzle), Some(_)));
+}
+
+#[test]
+fn broken_side_joins_hexagons_unsat() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 2,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 2 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 })],
+        broken: vec![IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 1, y: 1 },
+            dir: EdgeDirection::Vertical,
+        })],
+        dots: vec![],
+        squares: vec![
+            ColouredSymbol {
+                pos: Pos { x: 0, y: 1 },
+                colour: Colour::Black,
+            },
+            ColouredSymbol {
+                pos: Pos { x: 1, y: 1 },
+                colour: Colour::White,
+            },
+        ],
+        suns: vec![],
+        lattice: Lattice::Hexagonal,
+        ..Default::default()
+    };
+    assert_eq!(solve(&puzzle), None);
+}
+
+#[test]
+fn symbol_on_right_half_of_hexagon() {
+    let puzzle = Puzzle {
+        width: 3,
+        height: 2,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 2 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 })],
+        broken: vec![IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 1, y: 1 },
+            dir: EdgeDirection::Vertical,
+        })],
+        dots: vec![],
+        squares: vec![
+            ColouredSymbol {
+                pos: Pos { x: 0, y: 1 },
+                colour: Colour::Black,
+            },
+            ColouredSymbol {
+                pos: Pos { x: 2, y: 1 },
+                colour: Colour::White,
+            },
+        ],
+        suns: vec![],
+        lattice: Lattice::Hexagonal,
+        ..Default::default()
+    };
+    assert_eq!(solve(&puzzle), None);
+}
+
+#[test]
+fn line_follows_side_past_odd_row() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 2,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 2 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        broken: vec![IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 0, y: 2 },
+            dir: EdgeDirection::Horizontal,
+        })],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        lattice: Lattice::Hexagonal,
+        ..Default::default()
+    };
+    let lines = solve(&puzzle).unwrap();
+    assert_eq!(
+        lines[0][1],
+        IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 0, y: 1 },
+            dir: EdgeDirection::Vertical,
+        })
+    );
+}
@@ -0,0 +1,85 @@
+use witness::{solve, Edge, EdgeDirection, IntersectionOrEdge, LengthBound, Pos, Puzzle};
+
+#[test]
+fn at_most_picks_shortest_path() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        length: Some(LengthBound::AtMost(2)),
+        ..Default::default()
+    };
+    assert_eq!(
+        solve(&puzzle),
+        Some(vec![vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 0, y: 0 },
+                dir: EdgeDirection::Horizontal,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 1, y: 0 },
+                dir: EdgeDirection::Horizontal,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 }),
+        ]])
+    );
+}
+
+#[test]
+fn exact_length_sat() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        length: Some(LengthBound::Exactly(4)),
+        ..Default::default()
+    };
+    let line = solve(&puzzle).unwrap().remove(0);
+    assert_eq!(line.len(), 9);
+}
+
+#[test]
+fn exact_length_with_wrong_parity_unsat() {
+    let puzzle = Puzzle {
+        width: 1,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        length: Some(LengthBound::Exactly(3)),
+        ..Default::default()
+    };
+    assert_eq!(solve(&puzzle), None);
+}
+
+#[test]
+fn at_least_longer_than_grid_unsat() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        length: Some(LengthBound::AtLeast(5)),
+        ..Default::default()
+    };
+    assert_eq!(solve(&puzzle), None);
+}
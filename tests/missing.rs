@@ -0,0 +1,203 @@
+use witness::{
+    solve, solve_detailed, Colour, ColouredSymbol, Edge, EdgeDirection, IntersectionOrEdge,
+    Polyomino, PolyominoPlacement, Pos, Puzzle,
+};
+
+#[test]
+fn hole_separates_regions() {
+    let puzzle = Puzzle {
+        width: 3,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 3, y: 0 })],
+        broken: vec![],
+        missing_cells: vec![Pos { x: 1, y: 0 }],
+        dots: vec![],
+        squares: vec![
+            ColouredSymbol {
+                pos: Pos { x: 0, y: 0 },
+                colour: Colour::Black,
+            },
+            ColouredSymbol {
+                pos: Pos { x: 2, y: 0 },
+                colour: Colour::White,
+            },
+        ],
+        suns: vec![],
+        ..Default::default()
+    };
+    assert!(matches!(solve(&puzzle), Some(_)));
+}
+
+#[test]
+fn line_avoids_missing_intersection() {
+    let puzzle = Puzzle {
+        width: 1,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })],
+        broken: vec![],
+        missing: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 })],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        ..Default::default()
+    };
+    assert_eq!(
+        solve(&puzzle),
+        Some(vec![vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 0, y: 0 },
+                dir: EdgeDirection::Vertical,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 0, y: 1 },
+                dir: EdgeDirection::Horizontal,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 }),
+        ]])
+    );
+}
+
+#[test]
+fn missing_edge_separates_cells() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 })],
+        broken: vec![],
+        missing: vec![IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 1, y: 0 },
+            dir: EdgeDirection::Vertical,
+        })],
+        dots: vec![],
+        squares: vec![
+            ColouredSymbol {
+                pos: Pos { x: 0, y: 0 },
+                colour: Colour::Black,
+            },
+            ColouredSymbol {
+                pos: Pos { x: 1, y: 0 },
+                colour: Colour::White,
+            },
+        ],
+        suns: vec![],
+        ..Default::default()
+    };
+    assert!(matches!(solve(&puzzle), Some(_)));
+}
+
+#[test]
+fn polyomino_avoids_missing_cell() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 2,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 })],
+        broken: vec![],
+        missing_cells: vec![Pos { x: 1, y: 1 }],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        polyominoes: vec![Polyomino {
+            pos: Pos { x: 0, y: 0 },
+            shape: vec![Pos { x: 0, y: 1 }, Pos { x: 1, y: 1 }, Pos { x: 1, y: 0 }],
+            colour: Colour::Yellow,
+            rotatable: true,
+        }],
+        ..Default::default()
+    };
+    let solution = solve_detailed(&puzzle).unwrap();
+    assert_eq!(
+        solution.polyominoes,
+        vec![Some(PolyominoPlacement {
+            rotation: 2,
+            cells: vec![Pos { x: 1, y: 0 }, Pos { x: 0, y: 0 }, Pos { x: 0, y: 1 }],
+        })]
+    );
+}
+
+#[test]
+fn gap_does_not_separate_cells() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 })],
+        broken: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 })],
+        dots: vec![],
+        squares: vec![
+            ColouredSymbol {
+                pos: Pos { x: 0, y: 0 },
+                colour: Colour::Black,
+            },
+            ColouredSymbol {
+                pos: Pos { x: 1, y: 0 },
+                colour: Colour::White,
+            },
+        ],
+        suns: vec![],
+        ..Default::default()
+    };
+    assert_eq!(solve(&puzzle), None);
+}
+
+#[test]
+fn missing_border_intersection_separates_cells() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 })],
+        broken: vec![],
+        missing: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 })],
+        dots: vec![],
+        squares: vec![
+            ColouredSymbol {
+                pos: Pos { x: 0, y: 0 },
+                colour: Colour::Black,
+            },
+            ColouredSymbol {
+                pos: Pos { x: 1, y: 0 },
+                colour: Colour::White,
+            },
+        ],
+        suns: vec![],
+        ..Default::default()
+    };
+    assert_eq!(
+        solve(&puzzle),
+        Some(vec![vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 0, y: 0 },
+                dir: EdgeDirection::Vertical,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+        ]])
+    );
+}
+
+#[test]
+#[should_panic(expected = "Symbol on missing cell")]
+fn square_on_missing_cell() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 })],
+        broken: vec![],
+        missing_cells: vec![Pos { x: 1, y: 0 }],
+        dots: vec![],
+        squares: vec![ColouredSymbol {
+            pos: Pos { x: 1, y: 0 },
+            colour: Colour::Black,
+        }],
+        suns: vec![],
+        ..Default::default()
+    };
+    solve(&puzzle);
+}
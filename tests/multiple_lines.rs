@@ -0,0 +1,143 @@
+use witness::{
+    solve, Colour, ColouredSymbol, Edge, EdgeDirection, IntersectionOrEdge, Pos, Puzzle,
+};
+
+#[test]
+fn two_lines_sat() {
+    let puzzle = Puzzle {
+        width: 1,
+        height: 1,
+        sources: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 }),
+        ],
+        exits: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 }),
+        ],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        line_count: 2,
+        ..Default::default()
+    };
+    let lines = solve(&puzzle).unwrap();
+    assert_eq!(lines.len(), 2);
+    assert!(lines.contains(&vec![
+        IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+        IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 0, y: 0 },
+            dir: EdgeDirection::Vertical,
+        }),
+        IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+    ]));
+    assert!(lines.contains(&vec![
+        IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 }),
+        IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 1, y: 0 },
+            dir: EdgeDirection::Vertical,
+        }),
+        IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 }),
+    ]));
+}
+
+#[test]
+fn not_enough_sources() {
+    let puzzle = Puzzle {
+        width: 1,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 }),
+        ],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        line_count: 2,
+        ..Default::default()
+    };
+    assert_eq!(solve(&puzzle), None);
+}
+
+#[test]
+fn lines_cannot_overlap() {
+    let puzzle = Puzzle {
+        width: 1,
+        height: 1,
+        sources: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 }),
+        ],
+        exits: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 }),
+        ],
+        broken: vec![IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 1, y: 0 },
+            dir: EdgeDirection::Vertical,
+        })],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        line_count: 2,
+        ..Default::default()
+    };
+    assert_eq!(solve(&puzzle), None);
+}
+
+#[test]
+fn regions_separated_by_both_lines() {
+    let puzzle = Puzzle {
+        width: 3,
+        height: 1,
+        sources: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 }),
+        ],
+        exits: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 1 }),
+        ],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![
+            ColouredSymbol {
+                pos: Pos { x: 0, y: 0 },
+                colour: Colour::Black,
+            },
+            ColouredSymbol {
+                pos: Pos { x: 1, y: 0 },
+                colour: Colour::White,
+            },
+            ColouredSymbol {
+                pos: Pos { x: 2, y: 0 },
+                colour: Colour::Black,
+            },
+        ],
+        suns: vec![],
+        line_count: 2,
+        ..Default::default()
+    };
+    assert!(matches!(solve(&puzzle), Some(_)));
+}
+
+#[test]
+#[should_panic(expected = "Puzzles have at least one line")]
+fn no_lines() {
+    let puzzle = Puzzle {
+        width: 1,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        line_count: 0,
+        ..Default::default()
+    };
+    solve(&puzzle);
+}
@@ -0,0 +1,118 @@
+use witness::{solve, Colour, Edge, EdgeDirection, IntersectionOrEdge, Number, Pos, Puzzle};
+
+#[test]
+fn region_size_sat() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        numbers: vec![Number {
+            pos: Pos { x: 0, y: 0 },
+            count: 1,
+            colour: Colour::Black,
+        }],
+        ..Default::default()
+    };
+    assert_eq!(
+        solve(&puzzle),
+        Some(vec![vec![
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 1, y: 0 },
+                dir: EdgeDirection::Vertical,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 }),
+        ]])
+    );
+}
+
+#[test]
+fn region_size_unsat() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })],
+        broken: vec![IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 1, y: 0 },
+            dir: EdgeDirection::Vertical,
+        })],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        numbers: vec![Number {
+            pos: Pos { x: 0, y: 0 },
+            count: 1,
+            colour: Colour::Black,
+        }],
+        ..Default::default()
+    };
+    assert_eq!(solve(&puzzle), None);
+}
+
+#[test]
+fn equal_numbers_share_region() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })],
+        broken: vec![IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 1, y: 0 },
+            dir: EdgeDirection::Vertical,
+        })],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        numbers: vec![
+            Number {
+                pos: Pos { x: 0, y: 0 },
+                count: 2,
+                colour: Colour::Black,
+            },
+            Number {
+                pos: Pos { x: 1, y: 0 },
+                count: 2,
+                colour: Colour::Black,
+            },
+        ],
+        ..Default::default()
+    };
+    assert!(matches!(solve(&puzzle), Some(_)));
+}
+
+#[test]
+fn different_numbers_in_region_unsat() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })],
+        broken: vec![IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 1, y: 0 },
+            dir: EdgeDirection::Vertical,
+        })],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        numbers: vec![
+            Number {
+                pos: Pos { x: 0, y: 0 },
+                count: 2,
+                colour: Colour::Black,
+            },
+            Number {
+                pos: Pos { x: 1, y: 0 },
+                count: 1,
+                colour: Colour::Black,
+            },
+        ],
+        ..Default::default()
+    };
+    assert_eq!(solve(&puzzle), None);
+}
@@ -0,0 +1,236 @@
+use witness::{
+    solve_overlay, Colour, ColouredSymbol, Direction, Edge, EdgeDirection, ExitStub,
+    IntersectionOrEdge, Pos, Puzzle,
+};
+
+#[test]
+fn layers_share_one_line() {
+    let squares = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 2, y: 1 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![
+            ColouredSymbol {
+                pos: Pos { x: 0, y: 0 },
+                colour: Colour::Black,
+            },
+            ColouredSymbol {
+                pos: Pos { x: 1, y: 0 },
+                colour: Colour::White,
+            },
+        ],
+        suns: vec![],
+        ..Default::default()
+    };
+    let dots = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 2, y: 1 })],
+        broken: vec![],
+        dots: vec![IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 0, y: 1 },
+            dir: EdgeDirection::Horizontal,
+        })],
+        squares: vec![],
+        suns: vec![],
+        ..Default::default()
+    };
+    assert_eq!(
+        solve_overlay(&[squares, dots]),
+        Some(vec![vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 0, y: 0 },
+                dir: EdgeDirection::Vertical,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 0, y: 1 },
+                dir: EdgeDirection::Horizontal,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 1, y: 0 },
+                dir: EdgeDirection::Vertical,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 1, y: 0 },
+                dir: EdgeDirection::Horizontal,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 2, y: 0 },
+                dir: EdgeDirection::Vertical,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 1 }),
+        ]])
+    );
+}
+
+#[test]
+fn layers_in_conflict_unsat() {
+    let white_squares = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![
+            ColouredSymbol {
+                pos: Pos { x: 0, y: 0 },
+                colour: Colour::Black,
+            },
+            ColouredSymbol {
+                pos: Pos { x: 1, y: 0 },
+                colour: Colour::White,
+            },
+        ],
+        suns: vec![],
+        ..Default::default()
+    };
+    let suns = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![
+            ColouredSymbol {
+                pos: Pos { x: 0, y: 0 },
+                colour: Colour::Orange,
+            },
+            ColouredSymbol {
+                pos: Pos { x: 1, y: 0 },
+                colour: Colour::Orange,
+            },
+        ],
+        ..Default::default()
+    };
+    assert_eq!(solve_overlay(&[white_squares, suns]), None);
+}
+
+#[test]
+#[should_panic(expected = "Overlaid puzzles must have the same dimensions")]
+fn layers_with_different_dimensions() {
+    let small = Puzzle {
+        width: 1,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        ..Default::default()
+    };
+    let large = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 2, y: 1 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        ..Default::default()
+    };
+    solve_overlay(&[small, large]);
+}
+
+#[test]
+fn layers_list_stubs_in_different_orders() {
+    let dots = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 })],
+        exits: vec![
+            IntersectionOrEdge::ExitStub(ExitStub {
+                pos: Pos { x: 0, y: 0 },
+                dir: Direction::Up,
+            }),
+            IntersectionOrEdge::ExitStub(ExitStub {
+                pos: Pos { x: 2, y: 0 },
+                dir: Direction::Up,
+            }),
+        ],
+        broken: vec![],
+        dots: vec![IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 0, y: 0 },
+            dir: EdgeDirection::Vertical,
+        })],
+        squares: vec![],
+        suns: vec![],
+        ..Default::default()
+    };
+    let reordered = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 })],
+        exits: vec![
+            IntersectionOrEdge::ExitStub(ExitStub {
+                pos: Pos { x: 2, y: 0 },
+                dir: Direction::Up,
+            }),
+            IntersectionOrEdge::ExitStub(ExitStub {
+                pos: Pos { x: 0, y: 0 },
+                dir: Direction::Up,
+            }),
+        ],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        ..Default::default()
+    };
+    assert_eq!(
+        solve_overlay(&[dots, reordered]),
+        Some(vec![vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 0, y: 0 },
+                dir: EdgeDirection::Vertical,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+            IntersectionOrEdge::ExitStub(ExitStub {
+                pos: Pos { x: 0, y: 0 },
+                dir: Direction::Up,
+            }),
+        ]])
+    );
+}
+
+#[test]
+#[should_panic(expected = "Overlaid puzzles must have the same sources, exits")]
+fn layers_with_different_exits() {
+    let left = Puzzle {
+        width: 1,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        ..Default::default()
+    };
+    let right = Puzzle {
+        width: 1,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        ..Default::default()
+    };
+    solve_overlay(&[left, right]);
+}
@@ -0,0 +1,519 @@
+use witness::{
+    solve, solve_detailed, Colour, ColouredSymbol, Edge, EdgeDirection, IntersectionOrEdge,
+    Polyomino, PolyominoPlacement, Pos, Puzzle,
+};
+
+#[test]
+fn single_domino_sat() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 2,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 2, y: 1 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        polyominoes: vec![Polyomino {
+            pos: Pos { x: 0, y: 1 },
+            shape: vec![Pos { x: 0, y: 0 }, Pos { x: 1, y: 0 }],
+            colour: Colour::Yellow,
+            rotatable: false,
+        }],
+        ..Default::default()
+    };
+    assert_eq!(
+        solve(&puzzle),
+        Some(vec![vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 0, y: 1 },
+                dir: EdgeDirection::Horizontal,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 1, y: 1 },
+                dir: EdgeDirection::Horizontal,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 1 }),
+        ]])
+    );
+}
+
+#[test]
+fn fixed_orientation_sat() {
+    let puzzle = Puzzle {
+        width: 3,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 3, y: 0 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        polyominoes: vec![Polyomino {
+            pos: Pos { x: 1, y: 0 },
+            shape: vec![Pos { x: 0, y: 0 }, Pos { x: 1, y: 0 }, Pos { x: 2, y: 0 }],
+            colour: Colour::Yellow,
+            rotatable: false,
+        }],
+        ..Default::default()
+    };
+    assert!(matches!(solve(&puzzle), Some(_)));
+}
+
+#[test]
+fn fixed_orientation_unsat() {
+    let puzzle = Puzzle {
+        width: 3,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 3, y: 0 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        polyominoes: vec![Polyomino {
+            pos: Pos { x: 1, y: 0 },
+            shape: vec![Pos { x: 0, y: 0 }, Pos { x: 0, y: 1 }, Pos { x: 0, y: 2 }],
+            colour: Colour::Yellow,
+            rotatable: false,
+        }],
+        ..Default::default()
+    };
+    assert_eq!(solve(&puzzle), None);
+}
+
+#[test]
+fn sun_with_polyomino_sat() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![ColouredSymbol {
+            pos: Pos { x: 0, y: 0 },
+            colour: Colour::Yellow,
+        }],
+        polyominoes: vec![Polyomino {
+            pos: Pos { x: 1, y: 0 },
+            shape: vec![Pos { x: 0, y: 0 }, Pos { x: 1, y: 0 }],
+            colour: Colour::Yellow,
+            rotatable: false,
+        }],
+        ..Default::default()
+    };
+    assert!(matches!(solve(&puzzle), Some(_)));
+}
+
+#[test]
+fn sun_with_polyomino_unsat() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![ColouredSymbol {
+            pos: Pos { x: 0, y: 0 },
+            colour: Colour::Blue,
+        }],
+        polyominoes: vec![Polyomino {
+            pos: Pos { x: 1, y: 0 },
+            shape: vec![Pos { x: 0, y: 0 }, Pos { x: 1, y: 0 }],
+            colour: Colour::Yellow,
+            rotatable: false,
+        }],
+        ..Default::default()
+    };
+    assert_eq!(solve(&puzzle), None);
+}
+
+#[test]
+fn l_and_i_sat() {
+    let puzzle = Puzzle {
+        width: 4,
+        height: 4,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 4 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 4, y: 0 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        polyominoes: vec![
+            Polyomino {
+                pos: Pos { x: 1, y: 1 },
+                shape: vec![
+                    Pos { x: 0, y: 0 },
+                    Pos { x: 0, y: 1 },
+                    Pos { x: 0, y: 2 },
+                    Pos { x: 1, y: 2 },
+                ],
+                colour: Colour::Yellow,
+                rotatable: false,
+            },
+            Polyomino {
+                pos: Pos { x: 3, y: 3 },
+                shape: vec![
+                    Pos { x: 0, y: 0 },
+                    Pos { x: 0, y: 1 },
+                    Pos { x: 0, y: 2 },
+                    Pos { x: 0, y: 3 },
+                ],
+                colour: Colour::Yellow,
+                rotatable: false,
+            },
+        ],
+        ..Default::default()
+    };
+    assert!(matches!(solve(&puzzle), Some(_)));
+}
+
+#[test]
+fn square_and_i_share_region_sat() {
+    let puzzle = Puzzle {
+        width: 3,
+        height: 3,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 3 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 3, y: 0 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        polyominoes: vec![
+            Polyomino {
+                pos: Pos { x: 0, y: 0 },
+                shape: vec![
+                    Pos { x: 0, y: 0 },
+                    Pos { x: 1, y: 0 },
+                    Pos { x: 0, y: 1 },
+                    Pos { x: 1, y: 1 },
+                ],
+                colour: Colour::Yellow,
+                rotatable: false,
+            },
+            Polyomino {
+                pos: Pos { x: 2, y: 2 },
+                shape: vec![Pos { x: 0, y: 0 }, Pos { x: 1, y: 0 }, Pos { x: 2, y: 0 }],
+                colour: Colour::Yellow,
+                rotatable: false,
+            },
+        ],
+        ..Default::default()
+    };
+    assert!(matches!(solve(&puzzle), Some(_)));
+}
+
+#[test]
+fn opposite_corner_squares_unsat() {
+    let puzzle = Puzzle {
+        width: 4,
+        height: 4,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 4 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 4, y: 0 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        polyominoes: vec![
+            Polyomino {
+                pos: Pos { x: 0, y: 3 },
+                shape: vec![
+                    Pos { x: 0, y: 0 },
+                    Pos { x: 1, y: 0 },
+                    Pos { x: 0, y: 1 },
+                    Pos { x: 1, y: 1 },
+                ],
+                colour: Colour::Yellow,
+                rotatable: false,
+            },
+            Polyomino {
+                pos: Pos { x: 3, y: 0 },
+                shape: vec![
+                    Pos { x: 0, y: 0 },
+                    Pos { x: 1, y: 0 },
+                    Pos { x: 0, y: 1 },
+                    Pos { x: 1, y: 1 },
+                ],
+                colour: Colour::Yellow,
+                rotatable: false,
+            },
+        ],
+        ..Default::default()
+    };
+    assert_eq!(solve(&puzzle), None);
+}
+
+#[test]
+fn rotated_l_sat() {
+    let puzzle = Puzzle {
+        width: 3,
+        height: 3,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 3 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 3, y: 0 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        polyominoes: vec![Polyomino {
+            pos: Pos { x: 2, y: 0 },
+            shape: vec![
+                Pos { x: 0, y: 0 },
+                Pos { x: 0, y: 1 },
+                Pos { x: 0, y: 2 },
+                Pos { x: 1, y: 2 },
+            ],
+            colour: Colour::Yellow,
+            rotatable: true,
+        }],
+        ..Default::default()
+    };
+    assert!(matches!(solve(&puzzle), Some(_)));
+}
+
+#[test]
+fn unrotated_l_unsat() {
+    let puzzle = Puzzle {
+        width: 3,
+        height: 3,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 3 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 3, y: 0 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        polyominoes: vec![Polyomino {
+            pos: Pos { x: 2, y: 0 },
+            shape: vec![
+                Pos { x: 0, y: 0 },
+                Pos { x: 0, y: 1 },
+                Pos { x: 0, y: 2 },
+                Pos { x: 1, y: 2 },
+            ],
+            colour: Colour::Yellow,
+            rotatable: false,
+        }],
+        ..Default::default()
+    };
+    assert_eq!(solve(&puzzle), None);
+}
+
+#[test]
+fn rotation_reported() {
+    let puzzle = Puzzle {
+        width: 3,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 3, y: 0 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        polyominoes: vec![Polyomino {
+            pos: Pos { x: 1, y: 0 },
+            shape: vec![Pos { x: 0, y: 0 }, Pos { x: 0, y: 1 }, Pos { x: 0, y: 2 }],
+            colour: Colour::Yellow,
+            rotatable: true,
+        }],
+        ..Default::default()
+    };
+    let solution = solve_detailed(&puzzle).unwrap();
+    assert_eq!(
+        solution.polyominoes,
+        vec![Some(PolyominoPlacement {
+            rotation: 1,
+            cells: vec![Pos { x: 2, y: 0 }, Pos { x: 1, y: 0 }, Pos { x: 0, y: 0 }],
+        })]
+    );
+}
+
+#[test]
+fn negative_cancels_positive_sat() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        polyominoes: vec![Polyomino {
+            pos: Pos { x: 0, y: 0 },
+            shape: vec![Pos { x: 0, y: 0 }, Pos { x: 1, y: 0 }],
+            colour: Colour::Yellow,
+            rotatable: false,
+        }],
+        negative_polyominoes: vec![Polyomino {
+            pos: Pos { x: 1, y: 0 },
+            shape: vec![Pos { x: 0, y: 0 }, Pos { x: 1, y: 0 }],
+            colour: Colour::Blue,
+            rotatable: false,
+        }],
+        ..Default::default()
+    };
+    let solution = solve_detailed(&puzzle).unwrap();
+    assert_eq!(solution.polyominoes, vec![None]);
+    assert_eq!(solution.negative_polyominoes, vec![None]);
+}
+
+#[test]
+fn lone_negative_unsat() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        negative_polyominoes: vec![Polyomino {
+            pos: Pos { x: 1, y: 0 },
+            shape: vec![Pos { x: 0, y: 0 }],
+            colour: Colour::Blue,
+            rotatable: false,
+        }],
+        ..Default::default()
+    };
+    assert_eq!(solve(&puzzle), None);
+}
+
+#[test]
+fn overlapping_positives_unsat() {
+    let puzzle = Puzzle {
+        width: 3,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 3, y: 0 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        polyominoes: vec![
+            Polyomino {
+                pos: Pos { x: 0, y: 0 },
+                shape: vec![Pos { x: 0, y: 0 }, Pos { x: 1, y: 0 }],
+                colour: Colour::Yellow,
+                rotatable: false,
+            },
+            Polyomino {
+                pos: Pos { x: 2, y: 0 },
+                shape: vec![Pos { x: 0, y: 0 }, Pos { x: 1, y: 0 }],
+                colour: Colour::Yellow,
+                rotatable: false,
+            },
+        ],
+        ..Default::default()
+    };
+    assert_eq!(solve(&puzzle), None);
+}
+
+#[test]
+fn negative_fills_overlap_sat() {
+    let puzzle = Puzzle {
+        width: 3,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 3, y: 0 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        polyominoes: vec![
+            Polyomino {
+                pos: Pos { x: 0, y: 0 },
+                shape: vec![Pos { x: 0, y: 0 }, Pos { x: 1, y: 0 }],
+                colour: Colour::Yellow,
+                rotatable: false,
+            },
+            Polyomino {
+                pos: Pos { x: 2, y: 0 },
+                shape: vec![Pos { x: 0, y: 0 }, Pos { x: 1, y: 0 }],
+                colour: Colour::Yellow,
+                rotatable: false,
+            },
+        ],
+        negative_polyominoes: vec![Polyomino {
+            pos: Pos { x: 1, y: 0 },
+            shape: vec![Pos { x: 0, y: 0 }],
+            colour: Colour::Blue,
+            rotatable: false,
+        }],
+        ..Default::default()
+    };
+    let solution = solve_detailed(&puzzle).unwrap();
+    assert_eq!(
+        solution.negative_polyominoes,
+        vec![Some(PolyominoPlacement {
+            rotation: 0,
+            cells: vec![Pos { x: 1, y: 0 }],
+        })]
+    );
+}
+
+#[test]
+fn negative_outside_region_sat() {
+    let puzzle = Puzzle {
+        width: 3,
+        height: 2,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 3, y: 1 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        polyominoes: vec![
+            Polyomino {
+                pos: Pos { x: 0, y: 0 },
+                shape: vec![
+                    Pos { x: 0, y: 0 },
+                    Pos { x: 1, y: 0 },
+                    Pos { x: 0, y: 1 },
+                    Pos { x: 1, y: 1 },
+                ],
+                colour: Colour::Yellow,
+                rotatable: false,
+            },
+            Polyomino {
+                pos: Pos { x: 2, y: 0 },
+                shape: vec![Pos { x: 0, y: 0 }],
+                colour: Colour::Yellow,
+                rotatable: false,
+            },
+        ],
+        negative_polyominoes: vec![Polyomino {
+            pos: Pos { x: 1, y: 0 },
+            shape: vec![Pos { x: 0, y: 0 }, Pos { x: 1, y: 0 }],
+            colour: Colour::Blue,
+            rotatable: false,
+        }],
+        ..Default::default()
+    };
+    assert_eq!(
+        solve(&puzzle),
+        Some(vec![vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 0, y: 1 },
+                dir: EdgeDirection::Horizontal,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 1, y: 1 },
+                dir: EdgeDirection::Horizontal,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 1 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 2, y: 1 },
+                dir: EdgeDirection::Horizontal,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 3, y: 1 }),
+        ]])
+    );
+}
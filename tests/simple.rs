@@ -11,10 +11,14 @@ fn minimal_sat() {
         dots: vec![],
         squares: vec![],
         suns: vec![],
+        ..Default::default()
     };
     assert_eq!(
         solve(&puzzle),
-        Some(vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })])
+        Some(vec![vec![IntersectionOrEdge::Intersection(Pos {
+            x: 0,
+            y: 0
+        })]])
     );
 }
 
@@ -32,6 +36,7 @@ fn minimal_unsat() {
         dots: vec![],
         squares: vec![],
         suns: vec![],
+        ..Default::default()
     };
     assert_eq!(solve(&puzzle), None);
 }
@@ -50,10 +55,11 @@ fn simple_sat() {
         dots: vec![],
         squares: vec![],
         suns: vec![],
+        ..Default::default()
     };
     assert_eq!(
         solve(&puzzle),
-        Some(vec![
+        Some(vec![vec![
             IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
             IntersectionOrEdge::Edge(Edge {
                 pos: Pos { x: 0, y: 0 },
@@ -65,7 +71,7 @@ fn simple_sat() {
                 dir: EdgeDirection::Vertical
             }),
             IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })
-        ])
+        ]])
     );
 }
 
@@ -83,10 +89,11 @@ fn moving_upwards() {
         dots: vec![],
         squares: vec![],
         suns: vec![],
+        ..Default::default()
     };
     assert_eq!(
         solve(&puzzle),
-        Some(vec![
+        Some(vec![vec![
             IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 }),
             IntersectionOrEdge::Edge(Edge {
                 pos: Pos { x: 0, y: 1 },
@@ -98,7 +105,7 @@ fn moving_upwards() {
                 dir: EdgeDirection::Vertical
             }),
             IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 })
-        ])
+        ]])
     );
 }
 
@@ -116,6 +123,7 @@ fn simple_unsat() {
         dots: vec![],
         squares: vec![],
         suns: vec![],
+        ..Default::default()
     };
     assert_eq!(solve(&puzzle), None);
 }
@@ -154,6 +162,7 @@ fn sat_with_edges() {
         dots: vec![],
         squares: vec![],
         suns: vec![],
+        ..Default::default()
     };
     assert!(matches!(solve(&puzzle), Some(_)));
 }
@@ -196,6 +205,7 @@ fn unsat_with_edges() {
         dots: vec![],
         squares: vec![],
         suns: vec![],
+        ..Default::default()
     };
     assert_eq!(solve(&puzzle), None);
 }
@@ -214,6 +224,7 @@ fn no_source() {
         dots: vec![],
         squares: vec![],
         suns: vec![],
+        ..Default::default()
     };
     assert_eq!(solve(&puzzle), None);
 }
@@ -232,6 +243,7 @@ fn no_exit() {
         dots: vec![],
         squares: vec![],
         suns: vec![],
+        ..Default::default()
     };
     assert_eq!(solve(&puzzle), None);
 }
@@ -256,6 +268,7 @@ fn multiple_sources() {
         dots: vec![],
         squares: vec![],
         suns: vec![],
+        ..Default::default()
     };
     assert!(matches!(solve(&puzzle), Some(_)));
 }
@@ -283,6 +296,7 @@ fn multiple_exits() {
         dots: vec![],
         squares: vec![],
         suns: vec![],
+        ..Default::default()
     };
     assert!(matches!(solve(&puzzle), Some(_)));
 }
@@ -22,6 +22,7 @@ fn two_squares_sat() {
             },
         ],
         suns: vec![],
+        ..Default::default()
     };
     assert!(matches!(solve(&puzzle), Some(_)));
 }
@@ -49,10 +50,95 @@ fn two_squares_unsat() {
             },
         ],
         suns: vec![],
+        ..Default::default()
     };
     assert_eq!(solve(&puzzle), None);
 }
 
+#[test]
+fn same_shade_sat() {
+    let puzzle = Puzzle {
+        width: 1,
+        height: 2,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })],
+        broken: vec![IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 0, y: 1 },
+            dir: EdgeDirection::Horizontal,
+        })],
+        dots: vec![],
+        squares: vec![
+            ColouredSymbol {
+                pos: Pos { x: 0, y: 0 },
+                colour: Colour::Rgb(200, 0, 0),
+            },
+            ColouredSymbol {
+                pos: Pos { x: 0, y: 1 },
+                colour: Colour::Rgb(200, 0, 0),
+            },
+        ],
+        suns: vec![],
+        ..Default::default()
+    };
+    assert!(matches!(solve(&puzzle), Some(_)));
+}
+
+#[test]
+fn different_shades_unsat() {
+    let puzzle = Puzzle {
+        width: 1,
+        height: 2,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })],
+        broken: vec![IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 0, y: 1 },
+            dir: EdgeDirection::Horizontal,
+        })],
+        dots: vec![],
+        squares: vec![
+            ColouredSymbol {
+                pos: Pos { x: 0, y: 0 },
+                colour: Colour::Rgb(200, 0, 0),
+            },
+            ColouredSymbol {
+                pos: Pos { x: 0, y: 1 },
+                colour: Colour::Rgb(201, 0, 0),
+            },
+        ],
+        suns: vec![],
+        ..Default::default()
+    };
+    assert_eq!(solve(&puzzle), None);
+}
+
+#[test]
+fn named_colour_matches_its_shade_sat() {
+    let puzzle = Puzzle {
+        width: 1,
+        height: 2,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })],
+        broken: vec![IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 0, y: 1 },
+            dir: EdgeDirection::Horizontal,
+        })],
+        dots: vec![],
+        squares: vec![
+            ColouredSymbol {
+                pos: Pos { x: 0, y: 0 },
+                colour: Colour::Red,
+            },
+            ColouredSymbol {
+                pos: Pos { x: 0, y: 1 },
+                colour: Colour::Rgb(255, 0, 0),
+            },
+        ],
+        suns: vec![],
+        ..Default::default()
+    };
+    assert!(matches!(solve(&puzzle), Some(_)));
+}
+
 #[test]
 fn quarry_door_sat() {
     let puzzle = Puzzle {
@@ -129,6 +215,7 @@ fn quarry_door_sat() {
             },
         ],
         suns: vec![],
+        ..Default::default()
     };
     assert!(matches!(solve(&puzzle), Some(_)));
 }
@@ -209,6 +296,7 @@ fn quarry_door_unsat() {
             },
         ],
         suns: vec![],
+        ..Default::default()
     };
     let result = solve(&puzzle);
     println!("{:#?}", result);
@@ -242,6 +330,7 @@ fn square_with_suns_sat() {
                 colour: Colour::White,
             },
         ],
+        ..Default::default()
     };
     assert!(matches!(solve(&puzzle), Some(_)));
 }
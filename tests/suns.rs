@@ -22,6 +22,7 @@ fn two_suns_sat() {
             },
         ],
         suns: vec![],
+        ..Default::default()
     };
     assert!(matches!(solve(&puzzle), Some(_)));
 }
@@ -49,6 +50,7 @@ fn two_suns_unsat() {
                 colour: Colour::Orange,
             },
         ],
+        ..Default::default()
     };
     let result = solve(&puzzle);
     println!("{:#?}", result);
@@ -78,10 +80,11 @@ fn suns_and_squares_simple() {
             pos: Pos { x: 1, y: 1 },
             colour: Colour::White,
         }],
+        ..Default::default()
     };
     assert_eq!(
         solve(&puzzle),
-        Some(vec![
+        Some(vec![vec![
             IntersectionOrEdge::Intersection(Pos { x: 1, y: 2 }),
             IntersectionOrEdge::Edge(Edge {
                 pos: Pos { x: 1, y: 2 },
@@ -103,7 +106,7 @@ fn suns_and_squares_simple() {
                 dir: EdgeDirection::Vertical,
             }),
             IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 }),
-        ],)
+        ]],)
     );
 }
 
@@ -140,6 +143,7 @@ fn suns_and_squares_tricolour() {
             pos: Pos { x: 0, y: 1 },
             colour: Colour::Pink,
         }],
+        ..Default::default()
     };
     assert!(matches!(solve(&puzzle), Some(_)));
 }
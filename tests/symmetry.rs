@@ -0,0 +1,435 @@
+use witness::{
+    solve, solve_detailed, Colour, ColouredSymbol, Edge, EdgeDirection, IntersectionOrEdge,
+    LineColour, LineDot, Pos, Puzzle, Symmetry,
+};
+
+#[test]
+fn vertical_symmetry_sat() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 1 }),
+        ],
+        exits: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 }),
+        ],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        symmetry: Some(Symmetry::Vertical),
+        ..Default::default()
+    };
+    let left = vec![
+        IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+        IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 0, y: 0 },
+            dir: EdgeDirection::Vertical,
+        }),
+        IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+    ];
+    let right = vec![
+        IntersectionOrEdge::Intersection(Pos { x: 2, y: 1 }),
+        IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 2, y: 0 },
+            dir: EdgeDirection::Vertical,
+        }),
+        IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 }),
+    ];
+    let lines = solve(&puzzle).unwrap();
+    assert_eq!(lines.len(), 2);
+    assert!(lines.contains(&left));
+    assert!(lines.contains(&right));
+}
+
+#[test]
+fn horizontal_symmetry_sat() {
+    let puzzle = Puzzle {
+        width: 1,
+        height: 2,
+        sources: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 2 }),
+        ],
+        exits: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 2 }),
+        ],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        symmetry: Some(Symmetry::Horizontal),
+        ..Default::default()
+    };
+    let solution = solve_detailed(&puzzle).unwrap();
+    assert_eq!(solution.lines.len(), 2);
+    assert!(solution.lines.contains(&vec![
+        IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+        IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 0, y: 0 },
+            dir: EdgeDirection::Horizontal,
+        }),
+        IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 }),
+    ]));
+    assert!(solution.lines.contains(&vec![
+        IntersectionOrEdge::Intersection(Pos { x: 0, y: 2 }),
+        IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 0, y: 2 },
+            dir: EdgeDirection::Horizontal,
+        }),
+        IntersectionOrEdge::Intersection(Pos { x: 1, y: 2 }),
+    ]));
+}
+
+#[test]
+fn lines_cannot_meet_on_axis() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 1 }),
+        ],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        symmetry: Some(Symmetry::Vertical),
+        ..Default::default()
+    };
+    assert_eq!(solve(&puzzle), None);
+}
+
+#[test]
+fn broken_edge_blocks_mirrored_line() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 1 }),
+        ],
+        exits: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 }),
+        ],
+        broken: vec![IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 2, y: 0 },
+            dir: EdgeDirection::Vertical,
+        })],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        symmetry: Some(Symmetry::Vertical),
+        ..Default::default()
+    };
+    assert_eq!(solve(&puzzle), None);
+}
+
+#[test]
+fn both_lines_separate_regions() {
+    let puzzle = Puzzle {
+        width: 3,
+        height: 1,
+        sources: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 1 }),
+        ],
+        exits: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 0 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 }),
+        ],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![
+            ColouredSymbol {
+                pos: Pos { x: 0, y: 0 },
+                colour: Colour::Black,
+            },
+            ColouredSymbol {
+                pos: Pos { x: 1, y: 0 },
+                colour: Colour::White,
+            },
+            ColouredSymbol {
+                pos: Pos { x: 2, y: 0 },
+                colour: Colour::Black,
+            },
+        ],
+        suns: vec![],
+        symmetry: Some(Symmetry::Vertical),
+        ..Default::default()
+    };
+    assert!(matches!(solve(&puzzle), Some(_)));
+}
+
+#[test]
+fn rotational_symmetry_sat() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 1 }),
+        ],
+        exits: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 }),
+        ],
+        broken: vec![IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 1, y: 0 },
+            dir: EdgeDirection::Horizontal,
+        })],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        symmetry: Some(Symmetry::Rotational),
+        ..Default::default()
+    };
+    let lines = solve(&puzzle).unwrap();
+    assert_eq!(lines.len(), 2);
+    assert!(lines.contains(&vec![
+        IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+        IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 0, y: 0 },
+            dir: EdgeDirection::Vertical,
+        }),
+        IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+    ]));
+    assert!(lines.contains(&vec![
+        IntersectionOrEdge::Intersection(Pos { x: 2, y: 1 }),
+        IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 2, y: 0 },
+            dir: EdgeDirection::Vertical,
+        }),
+        IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 }),
+    ]));
+}
+
+#[test]
+fn rotational_symmetry_is_not_mirrored() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 1 }),
+        ],
+        exits: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 }),
+        ],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        symmetry: Some(Symmetry::Rotational),
+        ..Default::default()
+    };
+    assert_eq!(solve(&puzzle), None);
+}
+
+#[test]
+fn rotated_lines_cannot_meet_in_centre() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 2,
+        sources: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 2 }),
+        ],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        symmetry: Some(Symmetry::Rotational),
+        ..Default::default()
+    };
+    assert_eq!(solve(&puzzle), None);
+}
+
+#[test]
+fn blue_dot_on_blue_line() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 1 }),
+        ],
+        exits: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 }),
+        ],
+        broken: vec![],
+        dots: vec![],
+        line_dots: vec![LineDot {
+            pos: IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 0, y: 0 },
+                dir: EdgeDirection::Vertical,
+            }),
+            line: LineColour::Blue,
+        }],
+        squares: vec![],
+        suns: vec![],
+        symmetry: Some(Symmetry::Vertical),
+        ..Default::default()
+    };
+    let lines = solve(&puzzle).unwrap();
+    assert_eq!(
+        lines[0],
+        vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 0, y: 0 },
+                dir: EdgeDirection::Vertical,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+        ]
+    );
+}
+
+#[test]
+fn yellow_dot_on_yellow_line() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 1 }),
+        ],
+        exits: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 }),
+        ],
+        broken: vec![],
+        dots: vec![],
+        line_dots: vec![LineDot {
+            pos: IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 0, y: 0 },
+                dir: EdgeDirection::Vertical,
+            }),
+            line: LineColour::Yellow,
+        }],
+        squares: vec![],
+        suns: vec![],
+        symmetry: Some(Symmetry::Vertical),
+        ..Default::default()
+    };
+    let lines = solve(&puzzle).unwrap();
+    assert_eq!(
+        lines[1],
+        vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 0, y: 0 },
+                dir: EdgeDirection::Vertical,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+        ]
+    );
+}
+
+#[test]
+fn line_dots_on_wrong_line_unsat() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 1 }),
+        ],
+        exits: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 }),
+        ],
+        broken: vec![],
+        dots: vec![],
+        line_dots: vec![
+            LineDot {
+                pos: IntersectionOrEdge::Edge(Edge {
+                    pos: Pos { x: 0, y: 0 },
+                    dir: EdgeDirection::Vertical,
+                }),
+                line: LineColour::Yellow,
+            },
+            LineDot {
+                pos: IntersectionOrEdge::Edge(Edge {
+                    pos: Pos { x: 2, y: 0 },
+                    dir: EdgeDirection::Vertical,
+                }),
+                line: LineColour::Yellow,
+            },
+        ],
+        squares: vec![],
+        suns: vec![],
+        symmetry: Some(Symmetry::Vertical),
+        ..Default::default()
+    };
+    assert_eq!(solve(&puzzle), None);
+}
+
+#[test]
+#[should_panic(expected = "Yellow dots need a symmetry puzzle")]
+fn yellow_dot_without_symmetry() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 1 }),
+        ],
+        exits: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 }),
+        ],
+        broken: vec![],
+        dots: vec![],
+        line_dots: vec![LineDot {
+            pos: IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 0, y: 0 },
+                dir: EdgeDirection::Vertical,
+            }),
+            line: LineColour::Yellow,
+        }],
+        squares: vec![],
+        suns: vec![],
+        ..Default::default()
+    };
+    solve(&puzzle);
+}
+
+#[test]
+#[should_panic(expected = "Yellow dots need a symmetry puzzle")]
+fn yellow_dot_with_two_lines_without_symmetry() {
+    let puzzle = Puzzle {
+        width: 2,
+        height: 1,
+        sources: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 1 }),
+        ],
+        exits: vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 0 }),
+            IntersectionOrEdge::Intersection(Pos { x: 2, y: 0 }),
+        ],
+        broken: vec![],
+        dots: vec![],
+        line_dots: vec![LineDot {
+            pos: IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 0, y: 0 },
+                dir: EdgeDirection::Vertical,
+            }),
+            line: LineColour::Yellow,
+        }],
+        squares: vec![],
+        suns: vec![],
+        ..Default::default()
+    };
+    solve(&puzzle);
+}
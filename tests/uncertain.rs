@@ -0,0 +1,123 @@
+use witness::{
+    solvable_assignments, solve_uncertain, Candidate, Colour, ColouredSymbol, Edge, EdgeDirection,
+    IntersectionOrEdge, Pos, Puzzle, UncertainCell,
+};
+
+#[test]
+fn line_valid_for_every_candidate() {
+    let puzzle = Puzzle {
+        width: 1,
+        height: 2,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![ColouredSymbol {
+            pos: Pos { x: 0, y: 0 },
+            colour: Colour::Black,
+        }],
+        suns: vec![],
+        uncertain: vec![UncertainCell {
+            pos: Pos { x: 0, y: 1 },
+            candidates: vec![
+                Candidate::Square(Colour::Black),
+                Candidate::Square(Colour::White),
+            ],
+        }],
+        ..Default::default()
+    };
+    assert_eq!(
+        solve_uncertain(&puzzle),
+        Some(vec![vec![
+            IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 }),
+            IntersectionOrEdge::Edge(Edge {
+                pos: Pos { x: 0, y: 1 },
+                dir: EdgeDirection::Horizontal,
+            }),
+            IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 }),
+        ]])
+    );
+}
+
+#[test]
+fn no_line_valid_for_every_candidate() {
+    let puzzle = Puzzle {
+        width: 1,
+        height: 2,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })],
+        broken: vec![IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 0, y: 1 },
+            dir: EdgeDirection::Horizontal,
+        })],
+        dots: vec![],
+        squares: vec![ColouredSymbol {
+            pos: Pos { x: 0, y: 0 },
+            colour: Colour::Black,
+        }],
+        suns: vec![],
+        uncertain: vec![UncertainCell {
+            pos: Pos { x: 0, y: 1 },
+            candidates: vec![
+                Candidate::Square(Colour::Black),
+                Candidate::Square(Colour::White),
+            ],
+        }],
+        ..Default::default()
+    };
+    assert_eq!(solve_uncertain(&puzzle), None);
+}
+
+#[test]
+fn lists_solvable_assignments() {
+    let puzzle = Puzzle {
+        width: 1,
+        height: 2,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })],
+        broken: vec![IntersectionOrEdge::Edge(Edge {
+            pos: Pos { x: 0, y: 1 },
+            dir: EdgeDirection::Horizontal,
+        })],
+        dots: vec![],
+        squares: vec![ColouredSymbol {
+            pos: Pos { x: 0, y: 0 },
+            colour: Colour::Black,
+        }],
+        suns: vec![],
+        uncertain: vec![UncertainCell {
+            pos: Pos { x: 0, y: 1 },
+            candidates: vec![
+                Candidate::Empty,
+                Candidate::Square(Colour::White),
+                Candidate::Sun(Colour::Black),
+            ],
+        }],
+        ..Default::default()
+    };
+    assert_eq!(
+        solvable_assignments(&puzzle),
+        vec![vec![Candidate::Empty], vec![Candidate::Sun(Colour::Black)]]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Uncertain cells need at least one candidate")]
+fn uncertain_cell_without_candidates() {
+    let puzzle = Puzzle {
+        width: 1,
+        height: 1,
+        sources: vec![IntersectionOrEdge::Intersection(Pos { x: 0, y: 1 })],
+        exits: vec![IntersectionOrEdge::Intersection(Pos { x: 1, y: 1 })],
+        broken: vec![],
+        dots: vec![],
+        squares: vec![],
+        suns: vec![],
+        uncertain: vec![UncertainCell {
+            pos: Pos { x: 0, y: 0 },
+            candidates: vec![],
+        }],
+        ..Default::default()
+    };
+    solve_uncertain(&puzzle);
+}